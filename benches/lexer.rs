@@ -0,0 +1,63 @@
+//! Lexes a large generated source. To compare two revisions run
+//! `cargo bench --bench lexer -- --save-baseline before` on the first one and
+//! `cargo bench --bench lexer -- --baseline before` on the second.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use ariel::tokens::{source::SourceFile, token_type::TokenType, tokenizer};
+
+/// A chunk of code touching most of the lexer, repeated to look like a large
+/// generated source.
+const CHUNK: &str = r#"
+#: A point in space.
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+## computes something useless
+fn compute(point: Point, factor: i64) -> i64 {
+    let total: i64 = 0;
+    let name := "point {point.x} and {point.y}\n";
+
+    for i in 0..100 {
+        total += point.x * factor + 0xFF - 0b1010 << 2;
+        if total >= 1_000_000 && !done {
+            break;
+        }
+    }
+
+    #[ block comment #[ nested ]# ]#
+    return total / 3.14e2 + 'a';
+}
+"#;
+
+fn lex_all(contents: &str) -> usize {
+    let mut source = SourceFile::from_string("bench", contents.to_owned());
+    let mut count: usize = 0;
+
+    while tokenizer::get_token(&mut source).ttype != TokenType::Eof {
+        count += 1;
+    }
+
+    count
+}
+
+fn lexer_benchmark(c: &mut Criterion) {
+    let contents: String = CHUNK.repeat(2_000);
+
+    let mut group = c.benchmark_group("lexer");
+    group.throughput(Throughput::Bytes(contents.len() as u64));
+    group.bench_function("generated source", |b| {
+        b.iter(|| lex_all(black_box(&contents)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lexer_benchmark);
+criterion_main!(benches);
@@ -1,6 +1,9 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
 
-use crate::tokens::token::Token;
+use crate::tokens::{token::Token, token_type::TokenType};
 
 #[derive(Debug, Eq, Clone)]
 pub enum DataType {
@@ -17,6 +20,7 @@ pub enum DataType {
     F32,
     F64,
     String,
+    Char,
     Bool,
     Void,
     Array(Box<DataType>),
@@ -24,6 +28,80 @@ pub enum DataType {
     Compound { name: Box<Token> },
 }
 
+impl DataType {
+    pub fn from_primitive(ttype: &TokenType) -> Option<DataType> {
+        match ttype {
+            TokenType::U8 => Some(DataType::U8),
+            TokenType::U16 => Some(DataType::U16),
+            TokenType::U32 => Some(DataType::U32),
+            TokenType::U64 => Some(DataType::U64),
+            TokenType::Usize => Some(DataType::Usize),
+            TokenType::I8 => Some(DataType::I8),
+            TokenType::I16 => Some(DataType::I16),
+            TokenType::I32 => Some(DataType::I32),
+            TokenType::I64 => Some(DataType::I64),
+            TokenType::Isize => Some(DataType::Isize),
+            TokenType::F32 => Some(DataType::F32),
+            TokenType::F64 => Some(DataType::F64),
+            TokenType::Bool => Some(DataType::Bool),
+            TokenType::StringType => Some(DataType::String),
+            TokenType::CharType => Some(DataType::Char),
+            _ => None,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::U8
+                | DataType::U16
+                | DataType::U32
+                | DataType::U64
+                | DataType::Usize
+                | DataType::I8
+                | DataType::I16
+                | DataType::I32
+                | DataType::I64
+                | DataType::Isize
+        )
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, DataType::F32 | DataType::F64)
+    }
+
+    /// Whether the type holds negative values, i.e. whether it can be negated.
+    pub fn is_signed(&self) -> bool {
+        self.is_float()
+            || matches!(
+                self,
+                DataType::I8 | DataType::I16 | DataType::I32 | DataType::I64 | DataType::Isize
+            )
+    }
+
+    /// Whether a value of this type can appear in a string interpolation hole.
+    pub fn is_formattable(&self) -> bool {
+        self.is_integer()
+            || self.is_float()
+            || matches!(self, DataType::String | DataType::Char | DataType::Bool)
+    }
+
+    /// Smallest and biggest value of an integer type.
+    pub fn integer_range(&self) -> Option<(i128, i128)> {
+        match self {
+            DataType::U8 => Some((0, u8::MAX as i128)),
+            DataType::U16 => Some((0, u16::MAX as i128)),
+            DataType::U32 => Some((0, u32::MAX as i128)),
+            DataType::U64 | DataType::Usize => Some((0, u64::MAX as i128)),
+            DataType::I8 => Some((i8::MIN as i128, i8::MAX as i128)),
+            DataType::I16 => Some((i16::MIN as i128, i16::MAX as i128)),
+            DataType::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+            DataType::I64 | DataType::Isize => Some((i64::MIN as i128, i64::MAX as i128)),
+            _ => None,
+        }
+    }
+}
+
 impl PartialEq for DataType {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -40,6 +118,7 @@ impl PartialEq for DataType {
             | (DataType::F32, DataType::F32)
             | (DataType::F64, DataType::F64)
             | (DataType::String, DataType::String)
+            | (DataType::Char, DataType::Char)
             | (DataType::Bool, DataType::Bool)
             | (DataType::Void, DataType::Void) => true,
 
@@ -55,6 +134,23 @@ impl PartialEq for DataType {
     }
 }
 
+impl Hash for DataType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the types `PartialEq` holds the same have to hash the same
+        match self {
+            DataType::Usize => std::mem::discriminant(&DataType::U64).hash(state),
+            DataType::Isize => std::mem::discriminant(&DataType::I64).hash(state),
+            datatype => std::mem::discriminant(datatype).hash(state),
+        }
+
+        match self {
+            DataType::Array(of) | DataType::Pointer(of) => of.hash(state),
+            DataType::Compound { name } => name.lexeme.hash(state),
+            _ => {}
+        }
+    }
+}
+
 impl PartialOrd for DataType {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -71,6 +167,7 @@ impl PartialOrd for DataType {
             | (DataType::F32, DataType::F32)
             | (DataType::F64, DataType::F64)
             | (DataType::String, DataType::String)
+            | (DataType::Char, DataType::Char)
             | (DataType::Bool, DataType::Bool)
             | (DataType::Void, DataType::Void) => Some(std::cmp::Ordering::Equal),
 
@@ -112,6 +209,7 @@ impl Display for DataType {
             DataType::F32 => write!(f, "f32"),
             DataType::F64 => write!(f, "f64"),
             DataType::String => write!(f, "string"),
+            DataType::Char => write!(f, "char"),
             DataType::Bool => write!(f, "bool"),
             DataType::Void => write!(f, "void"),
             DataType::Array(of) => write!(f, "[{of}]"),
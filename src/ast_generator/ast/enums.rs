@@ -1,18 +1,40 @@
-use std::collections::HashMap;
-
 use crate::tokens::token::Token;
 
-use super::datatypes::DataType;
+use super::{datatypes::DataType, expressions::Expression};
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Enum {
-    pub name: Box<Token>,
-    pub variants: HashMap<Box<Token>, Option<DataType>>,
+    pub name: Token,
+    /// The integer type holding the discriminants, `enum Color: u8 { ... }`.
+    pub backing: Option<DataType>,
+    /// In declaration order.
+    pub variants: Vec<Variant>,
+    pub docs: Vec<Token>,
 }
 
 impl Enum {
-    pub fn new(name: Box<Token>, variants: HashMap<Box<Token>, Option<DataType>>) -> Self {
-        Self { name, variants }
+    pub fn new(
+        name: Token,
+        backing: Option<DataType>,
+        variants: Vec<Variant>,
+        docs: Vec<Token>,
+    ) -> Self {
+        Self {
+            name,
+            backing,
+            variants,
+            docs,
+        }
     }
 }
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct Variant {
+    pub name: Token,
+    pub datatype: Option<DataType>,
+    /// The explicit discriminant, `Red = 3`.
+    pub value: Option<Expression>,
+    pub docs: Vec<Token>,
+}
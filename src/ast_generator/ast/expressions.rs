@@ -1,31 +1,53 @@
 use std::fmt::Display;
 
-use crate::tokens::token::Token;
+use crate::tokens::{source_map::Span, token::Token, token_type::TokenType};
+
+use super::datatypes::DataType;
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub enum Expression {
     Name {
         line: usize,
         column: usize,
-        name: Box<Token>,
+        name: Token,
     },
     GetField {
         line: usize,
         column: usize,
         from: Box<Expression>,
-        get: Box<Token>,
+        get: Token,
+    },
+    Index {
+        line: usize,
+        column: usize,
+        /// From `[` to `]`.
+        span: Span,
+        from: Box<Expression>,
+        index: Box<Expression>,
+    },
+    /// `from[start..end]`, both bounds can be left out.
+    Slice {
+        line: usize,
+        column: usize,
+        /// From `[` to `]`.
+        span: Span,
+        from: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+        /// Whether `end` is part of the slice, as in `from[start..=end]`.
+        inclusive: bool,
     },
     Binary {
         line: usize,
         column: usize,
         left: Box<Expression>,
-        operation: Box<Token>,
+        operation: Token,
         right: Box<Expression>,
     },
     Unary {
         line: usize,
         column: usize,
-        operation: Box<Token>,
+        operation: Token,
         value: Box<Expression>,
     },
     FnCall {
@@ -37,7 +59,7 @@ pub enum Expression {
     Literal {
         line: usize,
         column: usize,
-        literal: Box<Token>,
+        literal: Token,
     },
     Nested {
         line: usize,
@@ -52,8 +74,10 @@ pub enum Expression {
     Sequence {
         line: usize,
         column: usize,
-        start: isize,
-        end: isize,
+        /// From the start of the first bound to the end of the last one.
+        span: Span,
+        start: i128,
+        end: i128,
     },
     AddressOf {
         line: usize,
@@ -63,7 +87,45 @@ pub enum Expression {
     ArrayLiteral {
         line: usize,
         column: usize,
-        values: Vec<Box<Expression>>,
+        values: Vec<Expression>,
+    },
+    Interpolation {
+        line: usize,
+        column: usize,
+        strings: Vec<Token>,
+        holes: Vec<Expression>,
+    },
+    /// `Enum::Variant` or `Enum::Variant(payload)`, in a match pattern the
+    /// payload is a pattern itself.
+    EnumVariant {
+        line: usize,
+        column: usize,
+        enumeration: Token,
+        variant: Token,
+        payload: Option<Box<Expression>>,
+    },
+    /// `first | second`, a match pattern matching when any of its patterns does.
+    Alternatives {
+        line: usize,
+        column: usize,
+        /// From the start of the first pattern to the end of the last one.
+        span: Span,
+        patterns: Vec<Expression>,
+    },
+    /// `value as Type`
+    Cast {
+        line: usize,
+        column: usize,
+        value: Box<Expression>,
+        to: DataType,
+    },
+    /// `Name { field: value }`, a shorthand field `Name { field }` takes the
+    /// value of the variable with the same name.
+    StructLiteral {
+        line: usize,
+        column: usize,
+        name: Token,
+        fields: Vec<(Token, Expression)>,
     },
 }
 
@@ -74,6 +136,25 @@ impl Display for Expression {
             Expression::GetField { from, get, .. } => {
                 write!(f, "get_field {} from {from}", get.lexeme)
             }
+            Expression::Index { from, index, .. } => write!(f, "index {index} of {from}"),
+            Expression::Slice {
+                from,
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                let mut str = String::from("slice [");
+                if let Some(start) = start {
+                    str.push_str(&format!("{start}"));
+                }
+                str.push_str(if *inclusive { "..=" } else { ".." });
+                if let Some(end) = end {
+                    str.push_str(&format!("{end}"));
+                }
+
+                write!(f, "{str}] of {from}")
+            }
             Expression::Binary {
                 left,
                 operation,
@@ -83,6 +164,7 @@ impl Display for Expression {
             Expression::Unary {
                 operation, value, ..
             } => write!(f, "{} {value}", operation.lexeme),
+            Expression::Cast { value, to, .. } => write!(f, "{value} as {to}"),
             Expression::FnCall {
                 fn_identifier,
                 args,
@@ -111,17 +193,124 @@ impl Display for Expression {
 
                 write!(f, "{str} ]")
             }
+            Expression::Interpolation { strings, holes, .. } => {
+                let mut str = String::from("interpolation \"");
+                for (string, hole) in strings.iter().zip(holes.iter()) {
+                    str.push_str(&format!("{}{{{hole}}}", string.lexeme));
+                }
+
+                if let Some(tail) = strings.last() {
+                    str.push_str(&tail.lexeme);
+                }
+
+                write!(f, "{str}\"")
+            }
+            Expression::EnumVariant {
+                enumeration,
+                variant,
+                payload,
+                ..
+            } => match payload {
+                Some(payload) => write!(f, "{}::{}({payload})", enumeration.lexeme, variant.lexeme),
+                None => write!(f, "{}::{}", enumeration.lexeme, variant.lexeme),
+            },
+            Expression::Alternatives { patterns, .. } => {
+                let patterns: Vec<String> = patterns
+                    .iter()
+                    .map(|pattern| format!("{pattern}"))
+                    .collect();
+
+                write!(f, "{}", patterns.join(" | "))
+            }
+            Expression::StructLiteral { name, fields, .. } => {
+                let mut str = format!("{} {{ ", name.lexeme);
+                for (field, value) in fields {
+                    str.push_str(&format!("{}: {value},", field.lexeme));
+                }
+
+                write!(f, "{str} }}")
+            }
         }
     }
 }
 
 impl Expression {
+    /// Whether the match pattern `self` matches every value `other` does,
+    /// positions aside.
+    pub fn covers(&self, other: &Expression) -> bool {
+        match (self, other) {
+            (_, Expression::Alternatives { patterns, .. }) => {
+                patterns.iter().all(|pattern| self.covers(pattern))
+            }
+            (Expression::Alternatives { patterns, .. }, _) => {
+                patterns.iter().any(|pattern| pattern.covers(other))
+            }
+
+            // both a binding and `_` match anything
+            (Expression::Name { .. }, _) => true,
+
+            (Expression::Literal { literal, .. }, Expression::Literal { literal: other, .. }) => {
+                literal.ttype == other.ttype
+                    && literal.lexeme == other.lexeme
+                    && literal.suffix == other.suffix
+            }
+            (
+                Expression::Sequence { start, end, .. },
+                Expression::Sequence {
+                    start: other_start,
+                    end: other_end,
+                    ..
+                },
+            ) => start <= other_start && other_end <= end,
+            (Expression::Sequence { start, end, .. }, Expression::Literal { literal, .. }) => {
+                literal.ttype == TokenType::Integer
+                    && literal
+                        .lexeme
+                        .parse::<i128>()
+                        .is_ok_and(|value| *start <= value && value <= *end)
+            }
+            (Expression::Literal { literal, .. }, Expression::Sequence { start, end, .. }) => {
+                literal.ttype == TokenType::Integer
+                    && start == end
+                    && literal.lexeme.parse::<i128>() == Ok(*start)
+            }
+
+            (
+                Expression::EnumVariant {
+                    enumeration,
+                    variant,
+                    payload,
+                    ..
+                },
+                Expression::EnumVariant {
+                    enumeration: other_enumeration,
+                    variant: other_variant,
+                    payload: other_payload,
+                    ..
+                },
+            ) => {
+                enumeration.lexeme == other_enumeration.lexeme
+                    && variant.lexeme == other_variant.lexeme
+                    && match (payload, other_payload) {
+                        (Some(payload), Some(other_payload)) => payload.covers(other_payload),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+
+            _ => false,
+        }
+    }
+
     pub fn line(&self) -> usize {
         match self {
             Expression::Name { line, .. } => *line,
             Expression::GetField { line, .. } => *line,
+            Expression::Index { line, .. } => *line,
+            Expression::Slice { line, .. } => *line,
             Expression::Binary { line, .. } => *line,
             Expression::Unary { line, .. } => *line,
+            Expression::Cast { line, .. } => *line,
             Expression::FnCall { line, .. } => *line,
             Expression::Literal { line, .. } => *line,
             Expression::Nested { line, .. } => *line,
@@ -129,6 +318,10 @@ impl Expression {
             Expression::Sequence { line, .. } => *line,
             Expression::AddressOf { line, .. } => *line,
             Expression::ArrayLiteral { line, .. } => *line,
+            Expression::Interpolation { line, .. } => *line,
+            Expression::EnumVariant { line, .. } => *line,
+            Expression::Alternatives { line, .. } => *line,
+            Expression::StructLiteral { line, .. } => *line,
         }
     }
 
@@ -136,8 +329,11 @@ impl Expression {
         match self {
             Expression::Name { column, .. } => *column,
             Expression::GetField { column, .. } => *column,
+            Expression::Index { column, .. } => *column,
+            Expression::Slice { column, .. } => *column,
             Expression::Binary { column, .. } => *column,
             Expression::Unary { column, .. } => *column,
+            Expression::Cast { column, .. } => *column,
             Expression::FnCall { column, .. } => *column,
             Expression::Literal { column, .. } => *column,
             Expression::Nested { column, .. } => *column,
@@ -145,6 +341,10 @@ impl Expression {
             Expression::Sequence { column, .. } => *column,
             Expression::AddressOf { column, .. } => *column,
             Expression::ArrayLiteral { column, .. } => *column,
+            Expression::Interpolation { column, .. } => *column,
+            Expression::EnumVariant { column, .. } => *column,
+            Expression::Alternatives { column, .. } => *column,
+            Expression::StructLiteral { column, .. } => *column,
         }
     }
 }
@@ -9,30 +9,33 @@ use crate::{
 #[allow(dead_code)]
 pub struct Function {
     is_main: bool,
-    pub name: Box<Token>,
-    pub args: Vec<(Box<Token>, DataType)>,
+    pub name: Token,
+    pub args: Vec<(Token, DataType)>,
     pub ret_type: Option<DataType>,
     pub body: Option<Vec<ScopeBoundStatement>>,
+    pub docs: Vec<Token>,
 }
 
 impl Function {
-    pub fn make_main(token: Box<Token>) -> Self {
+    pub fn make_main(token: Token) -> Self {
         Self {
             is_main: true,
             name: token,
             args: vec![],
             ret_type: None,
             body: None,
+            docs: vec![],
         }
     }
 
-    pub fn make_func(token: Box<Token>) -> Self {
+    pub fn make_func(token: Token) -> Self {
         Self {
             is_main: false,
             name: token,
             args: vec![],
             ret_type: None,
             body: None,
+            docs: vec![],
         }
     }
 }
@@ -1,7 +1,12 @@
-use std::collections::HashMap;
-
 use super::{expressions::Expression, variables::Variable};
 
+/// What a match arm matches: its pattern and the `if` guard that must also hold.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct MatchCase {
+    pub pattern: Expression,
+    pub guard: Option<Expression>,
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum ScopeBoundStatement {
     Scope {
@@ -48,7 +53,8 @@ pub enum ScopeBoundStatement {
         line: usize,
         column: usize,
         on: Expression,
-        cases: HashMap<Expression, Vec<ScopeBoundStatement>>,
+        /// In source order, the first arm matching the value is the one taken.
+        cases: Vec<(MatchCase, Vec<ScopeBoundStatement>)>,
     },
 
     Loop {
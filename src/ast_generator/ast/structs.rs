@@ -5,12 +5,21 @@ use super::datatypes::DataType;
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Struct {
-    pub name: Box<Token>,
-    pub fields: Vec<(Box<Token>, DataType)>,
+    pub name: Token,
+    pub fields: Vec<Field>,
+    pub docs: Vec<Token>,
 }
 
 impl Struct {
-    pub fn new(name: Box<Token>, fields: Vec<(Box<Token>, DataType)>) -> Self {
-        Self { name, fields }
+    pub fn new(name: Token, fields: Vec<Field>, docs: Vec<Token>) -> Self {
+        Self { name, fields, docs }
     }
 }
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct Field {
+    pub name: Token,
+    pub datatype: DataType,
+    pub docs: Vec<Token>,
+}
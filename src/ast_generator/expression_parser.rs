@@ -1,15 +1,36 @@
-use crate::tokens::{error::ParseError, token_type::TokenType};
+use crate::tokens::{error::ParseError, source_map::Span, token::Token, token_type::TokenType};
 
 use super::{ast::expressions::Expression, parser_head::ParserHead};
 
+/// Operators from the loosest to the tightest binding:
+///
+/// 1. `=` and the compound assignments `+=` `-=` `*=` `/=` `//=` `%=` `**=`
+///    `&=` `|=` `^=` `<<=` `>>=`, `a op= b` is the same as `a = a op b`
+/// 2. logical or `||`
+/// 3. logical and `&&`
+/// 4. `==` `!=`
+/// 5. `<` `<=` `>` `>=`
+/// 6. bitwise or `|` and xor `^`
+/// 7. bitwise and `&`
+/// 8. the shifts `<<` `>>`
+/// 9. `+` `-`
+/// 10. `*` `/`, integer division `//` and remainder `%`
+/// 11. casts `a as T`
+/// 12. prefix `!` `-` and bitwise not `~`
+/// 13. power `**`, `-2 ** 2` is `-(2 ** 2)`
+/// 14. postfix calls `f(x)`, field accesses `a.b`, indexing `a[i]` and slicing
+///     `a[lo..hi]` `a[lo..=hi]`
+///
+/// Binary operators are left associative except `**`, `2 ** 3 ** 2` is
+/// `2 ** (3 ** 2)`. Assignments can't be chained.
 pub fn parse_expression(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     let left: Box<Expression> = assignment_expression(head)?;
 
     match head.curr.ttype {
         TokenType::Question => Ok(Box::new(Expression::Monad {
             value: left,
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
         })),
         _ => Ok(left),
     }
@@ -19,14 +40,7 @@ pub fn assignment_expression(head: &mut ParserHead) -> Result<Box<Expression>, P
     let left: Box<Expression> = or_expression(head)?;
 
     match head.curr.ttype {
-        TokenType::Equal
-        | TokenType::PlusEquals
-        | TokenType::MinusEquals
-        | TokenType::StarEquals
-        | TokenType::SlashEquals
-        | TokenType::PowerEquals
-        | TokenType::ShiftLeftEqual
-        | TokenType::ShiftRightEqual => {
+        ref ttype if ttype.is_assignment() => {
             let operation = std::mem::take(&mut head.curr);
             head.advance();
             let value: Box<Expression> = or_expression(head)?;
@@ -35,15 +49,17 @@ pub fn assignment_expression(head: &mut ParserHead) -> Result<Box<Expression>, P
                 Expression::GetField {
                     from: _, get: _, ..
                 }
+                | Expression::Index { .. }
+                | Expression::Slice { .. }
                 | Expression::Name { name: _, .. } => Ok(Box::new(Expression::Binary {
                     left,
                     operation,
                     right: value,
-                    line: head.curr.line,
-                    column: head.curr.column,
+                    line: head.line(),
+                    column: head.column(),
                 })),
                 _ => Err(ParseError::InvalidAssignmentExpression {
-                    operation,
+                    operation: Box::new(operation),
                     assign_to: left,
                 }),
             }
@@ -55,13 +71,13 @@ pub fn assignment_expression(head: &mut ParserHead) -> Result<Box<Expression>, P
 pub fn or_expression(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     let mut left: Box<Expression> = and_expression(head)?;
 
-    while matches!(head.curr.ttype, TokenType::Or | TokenType::BitOr) {
+    while head.curr.ttype == TokenType::Or {
         head.advance();
 
         left = Box::new(Expression::Binary {
             left,
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             operation: std::mem::take(&mut head.prev),
             right: and_expression(head)?,
         });
@@ -73,14 +89,14 @@ pub fn or_expression(head: &mut ParserHead) -> Result<Box<Expression>, ParseErro
 pub fn and_expression(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     let mut left: Box<Expression> = equality_check(head)?;
 
-    while matches!(head.curr.ttype, TokenType::And | TokenType::BitAnd) {
+    while head.curr.ttype == TokenType::And {
         let operation = std::mem::take(&mut head.curr);
         head.advance();
 
         left = Box::new(Expression::Binary {
             left,
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             operation,
             right: equality_check(head)?,
         });
@@ -96,8 +112,8 @@ pub fn equality_check(head: &mut ParserHead) -> Result<Box<Expression>, ParseErr
         head.advance();
         left = Box::new(Expression::Binary {
             left,
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             operation: std::mem::take(&mut head.prev),
             right: comparison_check(head)?,
         });
@@ -107,7 +123,7 @@ pub fn equality_check(head: &mut ParserHead) -> Result<Box<Expression>, ParseErr
 }
 
 pub fn comparison_check(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
-    let mut left: Box<Expression> = term(head)?;
+    let mut left: Box<Expression> = bitwise_or(head)?;
 
     while matches!(
         head.curr.ttype,
@@ -118,33 +134,88 @@ pub fn comparison_check(head: &mut ParserHead) -> Result<Box<Expression>, ParseE
 
         left = Box::new(Expression::Binary {
             left,
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             operation,
-            right: term(head)?,
+            right: bitwise_or(head)?,
         });
     }
 
     Ok(left)
 }
 
-pub fn term(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
-    let mut left: Box<Expression> = factor(head)?;
+/// Bitwise operators bind tighter than comparisons, `a & 1 == 0` is
+/// `(a & 1) == 0`.
+pub fn bitwise_or(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let mut left: Box<Expression> = bitwise_and(head)?;
+
+    while matches!(head.curr.ttype, TokenType::BitOr | TokenType::BitXor) {
+        let operation = std::mem::take(&mut head.curr);
+        head.advance();
+
+        left = Box::new(Expression::Binary {
+            left,
+            line: head.line(),
+            column: head.column(),
+            operation,
+            right: bitwise_and(head)?,
+        });
+    }
+
+    Ok(left)
+}
+
+pub fn bitwise_and(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let mut left: Box<Expression> = shift(head)?;
+
+    while head.curr.ttype == TokenType::BitAnd {
+        let operation = std::mem::take(&mut head.curr);
+        head.advance();
+
+        left = Box::new(Expression::Binary {
+            left,
+            line: head.line(),
+            column: head.column(),
+            operation,
+            right: shift(head)?,
+        });
+    }
+
+    Ok(left)
+}
+
+pub fn shift(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let mut left: Box<Expression> = term(head)?;
 
     while matches!(
         head.curr.ttype,
-        TokenType::Plus
-            | TokenType::Minus
-            | TokenType::Mod
-            | TokenType::ShiftLeft
-            | TokenType::ShiftRight
+        TokenType::ShiftLeft | TokenType::ShiftRight
     ) {
         let operation = std::mem::take(&mut head.curr);
         head.advance();
 
         left = Box::new(Expression::Binary {
-            line: head.curr.line,
-            column: head.curr.column,
+            left,
+            line: head.line(),
+            column: head.column(),
+            operation,
+            right: term(head)?,
+        });
+    }
+
+    Ok(left)
+}
+
+pub fn term(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let mut left: Box<Expression> = factor(head)?;
+
+    while matches!(head.curr.ttype, TokenType::Plus | TokenType::Minus) {
+        let operation = std::mem::take(&mut head.curr);
+        head.advance();
+
+        left = Box::new(Expression::Binary {
+            line: head.line(),
+            column: head.column(),
             left,
             operation,
             right: factor(head)?,
@@ -155,42 +226,83 @@ pub fn term(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
 }
 
 pub fn factor(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
-    let mut left: Box<Expression> = unary(head)?;
+    let mut left: Box<Expression> = cast(head)?;
 
     while matches!(
         head.curr.ttype,
-        TokenType::Star | TokenType::Slash | TokenType::Power | TokenType::IntegerSlash
+        TokenType::Star | TokenType::Slash | TokenType::IntegerSlash | TokenType::Mod
     ) {
         let operation = std::mem::take(&mut head.curr);
         head.advance();
 
         left = Box::new(Expression::Binary {
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             left,
             operation,
-            right: unary(head)?,
+            right: cast(head)?,
         });
     }
 
     Ok(left)
 }
 
+/// A `*` right after the type is part of it and makes it a pointer, so
+/// `a as u8 * 2` doesn't parse: casting then multiplying is written
+/// `(a as u8) * 2`.
+pub fn cast(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let mut value: Box<Expression> = unary(head)?;
+
+    while matches!(head.curr.ttype, TokenType::As) {
+        head.advance();
+
+        value = Box::new(Expression::Cast {
+            line: head.line(),
+            column: head.column(),
+            value,
+            to: head.parse_datatype()?,
+        });
+    }
+
+    Ok(value)
+}
+
 pub fn unary(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     match head.curr.ttype {
-        TokenType::Not | TokenType::Minus => Ok(Box::new(Expression::Unary {
-            line: head.curr.line,
-            column: head.curr.column,
+        TokenType::Not | TokenType::Minus | TokenType::BitNot => Ok(Box::new(Expression::Unary {
+            line: head.line(),
+            column: head.column(),
             operation: std::mem::take(&mut head.curr),
             value: {
                 head.advance();
                 unary(head)?
             },
         })),
-        _ => call(head),
+        _ => power(head),
     }
 }
 
+/// The exponent is parsed as a prefix operation, which itself can be a power,
+/// making `**` right associative and letting `2 ** -1` through.
+pub fn power(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
+    let base: Box<Expression> = call(head)?;
+
+    if head.curr.ttype != TokenType::Power {
+        return Ok(base);
+    }
+
+    let operation = std::mem::take(&mut head.curr);
+    head.advance();
+
+    Ok(Box::new(Expression::Binary {
+        left: base,
+        line: head.line(),
+        column: head.column(),
+        operation,
+        right: unary(head)?,
+    }))
+}
+
 pub fn call(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     let mut expr: Box<Expression> = get(head)?;
 
@@ -199,18 +311,18 @@ pub fn call(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
         head.advance();
 
         if !matches!(head.curr.ttype, TokenType::RightParen) {
-            args.push(*parse_expression(head)?);
+            args.push(*head.with_struct_literals(true, parse_expression)?);
 
             while !matches!(head.curr.ttype, TokenType::Comma) {
                 head.advance();
-                args.push(*parse_expression(head)?);
+                args.push(*head.with_struct_literals(true, parse_expression)?);
             }
         }
 
         head.require_current_is(TokenType::RightParen)?;
         expr = Box::new(Expression::FnCall {
-            line: head.curr.line,
-            column: head.curr.column,
+            line: head.line(),
+            column: head.column(),
             fn_identifier: expr,
             args,
         });
@@ -222,50 +334,171 @@ pub fn call(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
 pub fn get(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     let mut expr: Box<Expression> = primary(head)?;
 
-    while matches!(
-        head.curr.ttype,
-        TokenType::Dot | TokenType::StaticScopeGetter
-    ) {
-        head.advance();
+    loop {
+        match head.curr.ttype {
+            TokenType::Dot | TokenType::StaticScopeGetter => {
+                head.advance();
 
-        head.require_current_is(TokenType::Identifier)?;
-        let property = std::mem::take(&mut head.curr);
-        head.advance();
+                head.require_current_is(TokenType::Identifier)?;
+                let property = std::mem::take(&mut head.curr);
+                head.advance();
 
-        expr = Box::new(Expression::GetField {
-            line: head.curr.line,
-            column: head.curr.column,
-            from: expr,
-            get: property,
-        });
+                expr = Box::new(Expression::GetField {
+                    line: head.line(),
+                    column: head.column(),
+                    from: expr,
+                    get: property,
+                });
+            }
+            TokenType::LeftSquare => {
+                head.advance();
+                expr = index(head, expr)?;
+            }
+            _ => return Ok(expr),
+        }
     }
+}
+
+/// Parses what follows the `[` of `from[index]`, `from[start..end]` or
+/// `from[start..=end]`. The bounds of a `..` slice can be left out.
+fn index(head: &mut ParserHead, from: Box<Expression>) -> Result<Box<Expression>, ParseError> {
+    let bracket: Span = head.prev.span;
+    let start: Option<Box<Expression>> = match head.curr.ttype {
+        TokenType::SequenceUpTo | TokenType::SequenceUpToIncluding => None,
+        _ => {
+            let index: Box<Expression> = or_expression(head)?;
+
+            if !matches!(
+                head.curr.ttype,
+                TokenType::SequenceUpTo | TokenType::SequenceUpToIncluding
+            ) {
+                head.require_current_is(TokenType::RightSquare)?;
+                head.advance();
 
-    return Ok(expr);
+                return Ok(Box::new(Expression::Index {
+                    line: head.line(),
+                    column: head.column(),
+                    span: Span::new(bracket.file, bracket.start, head.prev.span.end),
+                    from,
+                    index,
+                }));
+            }
+
+            Some(index)
+        }
+    };
+
+    let inclusive: bool = head.curr.ttype == TokenType::SequenceUpToIncluding;
+    head.advance();
+
+    let end: Option<Box<Expression>> = match head.curr.ttype {
+        TokenType::RightSquare if !inclusive => None,
+        _ => Some(or_expression(head)?),
+    };
+
+    head.require_current_is(TokenType::RightSquare)?;
+    head.advance();
+
+    Ok(Box::new(Expression::Slice {
+        line: head.line(),
+        column: head.column(),
+        span: Span::new(bracket.file, bracket.start, head.prev.span.end),
+        from,
+        start,
+        end,
+        inclusive,
+    }))
 }
 
 pub fn primary(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
     match head.curr.ttype {
-        TokenType::Identifier | TokenType::DontCare => {
+        TokenType::Identifier => {
+            head.advance();
+
+            match head.curr.ttype {
+                TokenType::LeftBrace if head.struct_literals => {
+                    let name = std::mem::take(&mut head.prev);
+                    head.advance();
+                    struct_literal(head, name)
+                }
+                TokenType::StaticScopeGetter => {
+                    let enumeration = std::mem::take(&mut head.prev);
+                    head.advance();
+                    let variant = enum_variant(head, enumeration, |head| {
+                        Ok(*head.with_struct_literals(true, parse_expression)?)
+                    })?;
+
+                    Ok(Box::new(variant))
+                }
+                _ => Ok(Box::new(Expression::Name {
+                    line: head.line(),
+                    column: head.column(),
+                    name: std::mem::take(&mut head.prev),
+                })),
+            }
+        }
+        TokenType::DontCare => {
             head.advance();
             Ok(Box::new(Expression::Name {
-                line: head.curr.line,
-                column: head.curr.column,
+                line: head.line(),
+                column: head.column(),
                 name: std::mem::take(&mut head.prev),
             }))
         }
         TokenType::Integer
         | TokenType::Double
         | TokenType::String
+        | TokenType::Char
         | TokenType::True
         | TokenType::False
         | TokenType::Nil => {
             head.advance();
             Ok(Box::new(Expression::Literal {
-                line: head.curr.line,
-                column: head.curr.column,
+                line: head.line(),
+                column: head.column(),
                 literal: std::mem::take(&mut head.prev),
             }))
         }
+        TokenType::StringHead => {
+            let line = head.line();
+            let column = head.column();
+
+            let mut strings = vec![std::mem::take(&mut head.curr)];
+            let mut holes: Vec<Expression> = vec![];
+            head.advance();
+
+            loop {
+                holes.push(*parse_expression(head)?);
+
+                match head.curr.ttype {
+                    TokenType::StringMiddle => {
+                        strings.push(std::mem::take(&mut head.curr));
+                        head.advance();
+                    }
+                    TokenType::StringTail => {
+                        strings.push(std::mem::take(&mut head.curr));
+                        head.advance();
+                        break;
+                    }
+                    _ => {
+                        return Err(ParseError::UnexpectedToken {
+                            token: Box::new(std::mem::take(&mut head.curr)),
+                            expected: TokenType::RightBrace,
+                            msg: Some(String::from(
+                                "An interpolation hole should contain a single expression closed by a `}`.",
+                            )),
+                        });
+                    }
+                }
+            }
+
+            Ok(Box::new(Expression::Interpolation {
+                line,
+                column,
+                strings,
+                holes,
+            }))
+        }
         TokenType::BitAnd => {
             head.advance();
 
@@ -275,18 +508,20 @@ pub fn primary(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
                 Expression::Name { .. } => Ok(Box::new(Expression::AddressOf {
                     of,
 
-                    line: head.curr.line,
-                    column: head.curr.column,
+                    line: head.line(),
+                    column: head.column(),
                 })),
-                _ => Err(ParseError::InvalidAddressOfValue { at: next_tk }),
+                _ => Err(ParseError::InvalidAddressOfValue {
+                    at: Box::new(next_tk),
+                }),
             }
         }
         TokenType::LeftSquare => {
             head.advance();
-            let mut values: Vec<Box<Expression>> = vec![];
+            let mut values: Vec<Expression> = vec![];
 
             while !matches!(head.curr.ttype, TokenType::RightSquare) {
-                values.push(parse_expression(head)?);
+                values.push(*head.with_struct_literals(true, parse_expression)?);
 
                 match head.curr.ttype {
                     TokenType::RightSquare => break,
@@ -295,7 +530,7 @@ pub fn primary(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
                     }
                     _ => {
                         return Err(ParseError::UnexpectedToken {
-                            token: std::mem::take(&mut head.curr),
+                            token: Box::new(std::mem::take(&mut head.curr)),
                             expected: TokenType::RightSquare,
                             msg: None,
                         });
@@ -309,48 +544,175 @@ pub fn primary(head: &mut ParserHead) -> Result<Box<Expression>, ParseError> {
             Ok(Box::new(Expression::ArrayLiteral {
                 values,
 
-                line: head.curr.line,
-                column: head.curr.column,
+                line: head.line(),
+                column: head.column(),
             }))
         }
         TokenType::LeftParen => {
             head.advance();
-            let nested: Box<Expression> = parse_expression(head)?;
+            let nested: Box<Expression> = head.with_struct_literals(true, parse_expression)?;
 
             head.require_current_is(TokenType::RightParen)?;
             head.advance();
             Ok(Box::new(Expression::Nested {
                 nested,
 
-                line: head.curr.line,
-                column: head.curr.column,
+                line: head.line(),
+                column: head.column(),
             }))
         }
         _ => Err(ParseError::InvalidExpression {
-            token: std::mem::take(&mut head.curr),
+            token: Box::new(std::mem::take(&mut head.curr)),
         }),
     }
 }
 
+/// Parses the fields of a struct literal up to its closing `}`, the `{` has
+/// already been consumed.
+fn struct_literal(head: &mut ParserHead, name: Token) -> Result<Box<Expression>, ParseError> {
+    let mut fields: Vec<(Token, Expression)> = vec![];
+
+    while !matches!(head.curr.ttype, TokenType::RightBrace) {
+        head.require_current_is(TokenType::Identifier)?;
+        let field = std::mem::take(&mut head.curr);
+        head.advance();
+
+        let value: Expression = match head.curr.ttype {
+            TokenType::Colon => {
+                head.advance();
+                *head.with_struct_literals(true, parse_expression)?
+            }
+            _ => Expression::Name {
+                line: head.line(),
+                column: head.column(),
+                name: field.clone(),
+            },
+        };
+        fields.push((field, value));
+
+        match head.curr.ttype {
+            TokenType::RightBrace => break,
+            TokenType::Comma => {
+                head.advance();
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    token: Box::new(std::mem::take(&mut head.curr)),
+                    expected: TokenType::RightBrace,
+                    msg: None,
+                });
+            }
+        }
+    }
+
+    head.require_current_is(TokenType::RightBrace)?;
+    head.advance();
+
+    Ok(Box::new(Expression::StructLiteral {
+        line: head.line(),
+        column: head.column(),
+        name,
+        fields,
+    }))
+}
+
+/// Parses what follows the `::` of `Enum::Variant` or `Enum::Variant(payload)`,
+/// the payload is parsed with `payload`.
+fn enum_variant(
+    head: &mut ParserHead,
+    enumeration: Token,
+    payload: impl FnOnce(&mut ParserHead) -> Result<Expression, ParseError>,
+) -> Result<Expression, ParseError> {
+    head.require_current_is(TokenType::Identifier)?;
+    let variant = std::mem::take(&mut head.curr);
+    head.advance();
+
+    let payload: Option<Box<Expression>> = match head.curr.ttype {
+        TokenType::LeftParen => {
+            head.advance();
+            let payload: Expression = payload(head)?;
+
+            head.require_current_is(TokenType::RightParen)?;
+            head.advance();
+
+            Some(Box::new(payload))
+        }
+        _ => None,
+    };
+
+    Ok(Expression::EnumVariant {
+        line: head.line(),
+        column: head.column(),
+        enumeration,
+        variant,
+        payload,
+    })
+}
+
+/// Patterns are names binding the matched value, `_`, literals, integer
+/// ranges and enum variants destructuring their payload with a nested pattern.
+/// Any of them can be joined with `|` to match if either does.
 pub fn match_pattern_expression(head: &mut ParserHead) -> Result<Expression, ParseError> {
+    let line = head.line();
+    let column = head.column();
+    let start: Span = head.curr.span;
+    let first: Expression = single_pattern(head)?;
+
+    if head.curr.ttype != TokenType::BitOr {
+        return Ok(first);
+    }
+
+    let mut patterns: Vec<Expression> = vec![first];
+    while head.curr.ttype == TokenType::BitOr {
+        head.advance();
+        patterns.push(single_pattern(head)?);
+    }
+
+    Ok(Expression::Alternatives {
+        line,
+        column,
+        span: Span::new(start.file, start.start, head.prev.span.end),
+        patterns,
+    })
+}
+
+fn single_pattern(head: &mut ParserHead) -> Result<Expression, ParseError> {
     match head.curr.ttype {
-        TokenType::Identifier | TokenType::DontCare => {
+        TokenType::Identifier => {
+            head.advance();
+
+            match head.curr.ttype {
+                TokenType::StaticScopeGetter => {
+                    let enumeration = std::mem::take(&mut head.prev);
+                    head.advance();
+                    enum_variant(head, enumeration, match_pattern_expression)
+                }
+                _ => Ok(Expression::Name {
+                    line: head.line(),
+                    column: head.column(),
+                    name: head.prev.clone(),
+                }),
+            }
+        }
+        TokenType::DontCare => {
             head.advance();
             Ok(Expression::Name {
-                line: head.curr.line,
-                column: head.curr.column,
-                name: std::mem::take(&mut head.prev),
+                line: head.line(),
+                column: head.column(),
+                name: head.prev.clone(),
             })
         }
         TokenType::Integer => {
             head.advance();
             match head.curr.ttype {
                 TokenType::SequenceUpTo | TokenType::SequenceUpToIncluding => {
-                    let start: isize = head.prev.lexeme.parse().unwrap();
+                    let first: Span = head.prev.span;
+                    // the lexer only lets through integers fitting in a u64
+                    let start: i128 = head.prev.lexeme.parse().unwrap();
                     head.advance();
 
                     head.require_current_is(TokenType::Integer)?;
-                    let end: isize = head.curr.lexeme.parse::<isize>().unwrap()
+                    let end: i128 = head.curr.lexeme.parse::<i128>().unwrap()
                         + if matches!(head.prev.ttype, TokenType::SequenceUpTo) {
                             -1
                         } else {
@@ -360,33 +722,35 @@ pub fn match_pattern_expression(head: &mut ParserHead) -> Result<Expression, Par
                     head.advance();
 
                     Ok(Expression::Sequence {
+                        span: Span::new(first.file, first.start, head.prev.span.end),
                         start,
                         end,
-                        line: head.curr.line,
-                        column: head.curr.column,
+                        line: head.line(),
+                        column: head.column(),
                     })
                 }
                 _ => Ok(Expression::Literal {
-                    line: head.curr.line,
-                    column: head.curr.column,
-                    literal: std::mem::take(&mut head.prev),
+                    line: head.line(),
+                    column: head.column(),
+                    literal: head.prev.clone(),
                 }),
             }
         }
         TokenType::Double
         | TokenType::String
+        | TokenType::Char
         | TokenType::True
         | TokenType::False
         | TokenType::Nil => {
             head.advance();
             Ok(Expression::Literal {
-                line: head.curr.line,
-                column: head.curr.column,
-                literal: std::mem::take(&mut head.prev),
+                line: head.line(),
+                column: head.column(),
+                literal: head.prev.clone(),
             })
         }
         _ => Err(ParseError::InvalidExpression {
-            token: std::mem::take(&mut head.curr),
+            token: Box::new(std::mem::take(&mut head.curr)),
         }),
     }
 }
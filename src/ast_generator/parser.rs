@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::VecDeque,
     sync::{Arc, Mutex},
     thread::{self, JoinHandle},
 };
@@ -8,12 +8,20 @@ use colored::Colorize;
 
 use crate::{
     ast_generator::{
-        ast::{datatypes::DataType, enums::Enum, function::Function},
+        ast::{
+            datatypes::DataType,
+            enums::{Enum, Variant},
+            expressions::Expression,
+            function::Function,
+        },
+        expression_parser,
         statement_parser::parse_scope_block,
     },
     tokens::{
         error::{Error, ParseError},
+        interner::Lexeme,
         source::SourceFile,
+        source_map::SourceMap,
         token::Token,
         token_type::TokenType,
         tokenizer,
@@ -21,52 +29,73 @@ use crate::{
 };
 
 use super::{
-    ast::{structs::Struct, ASTs},
+    ast::{
+        structs::{Field, Struct},
+        ASTs,
+    },
     parser_head::ParserHead,
     utils,
 };
 
-pub fn parse(path: &str, imported_files: Arc<Mutex<HashSet<String>>>) -> ASTs {
-    let mut ast = ASTs::new();
-
-    let mut mutex_data = match imported_files.lock() {
-        Ok(data) => data,
+/// Parses the file at `path` and, on their own threads, every file it
+/// imports. Each file is registered in `source_map` before being parsed, so
+/// a file imported more than once is parsed only the first time.
+pub fn parse(path: &str, source_map: Arc<Mutex<SourceMap>>) -> ASTs {
+    // the map stays locked until the file is registered so that two threads
+    // importing the same file can't both parse it
+    let mut map = match source_map.lock() {
+        Ok(map) => map,
         Err(e) => {
             eprintln!("[{path}] :: {e}");
-            return ast;
+            return ASTs::new();
         }
     };
 
-    if mutex_data.contains(path) {
+    if map.find(path).is_some() {
         eprintln!(
             "{} `{path}` has already been included. Skipping it.",
             "[Warning]".bold().yellow()
         );
-        return ast;
-    } else {
-        mutex_data.insert(path.to_owned());
-        drop(mutex_data);
+        return ASTs::new();
     }
 
-    let mut source = match SourceFile::new(path) {
+    let source = match path {
+        "-" => SourceFile::from_stdin(),
+        path => SourceFile::new(path),
+    };
+
+    let mut source = match source {
         Ok(source) => source,
         Err(e) => match e {
             Error::FileNotFound(source, msg) | Error::MemoryMapFiled(source, msg) => {
                 eprintln!("[{source}] :: {msg}");
-                return ast;
+                return ASTs::new();
+            }
+            Error::StdinUnreadable(msg) => {
+                eprintln!("[<stdin>] :: {msg}");
+                return ASTs::new();
             }
         },
     };
 
+    map.add(&mut source);
+    drop(map);
+
+    parse_source(&mut source, source_map)
+}
+
+/// Parses the already loaded `source` and, on their own threads, every file
+/// it imports.
+pub fn parse_source(source: &mut SourceFile, source_map: Arc<Mutex<SourceMap>>) -> ASTs {
+    let mut ast = ASTs::new();
     let mut handlers: VecDeque<JoinHandle<ASTs>> = VecDeque::new();
-    let mut head: ParserHead = ParserHead::new(
-        tokenizer::get_token(&mut source),
-        Box::new(Token::new()),
-        &mut source,
-    );
+    let mut head: ParserHead = ParserHead::new(tokenizer::get_token(source), Token::new(), source);
 
     // Actual parse loop
-    parse_global_stmt(&mut head, &mut ast, imported_files, &mut handlers, path);
+    parse_global_stmt(&mut head, &mut ast, source_map, &mut handlers);
+    for e in std::mem::take(&mut head.diagnostics) {
+        utils::print_error(head.source, &head.prev.lexeme, e);
+    }
 
     // After the parse loop wait for the other threads to finish if there are any
     while let Some(handle) = handlers.pop_front() {
@@ -86,9 +115,8 @@ pub fn parse(path: &str, imported_files: Arc<Mutex<HashSet<String>>>) -> ASTs {
 fn parse_global_stmt(
     head: &mut ParserHead,
     ast: &mut ASTs,
-    imported_files: Arc<Mutex<HashSet<String>>>,
+    source_map: Arc<Mutex<SourceMap>>,
     thread_handles: &mut VecDeque<JoinHandle<ASTs>>,
-    curr_file_name: &str,
 ) {
     loop {
         match head.curr.ttype {
@@ -98,23 +126,23 @@ fn parse_global_stmt(
 
                 match head.require_current_is(TokenType::String) {
                     Ok(_) => {
-                        // need to be cloned because of the move in the closure
-                        let imported_path = head.curr.lexeme.clone();
-                        let imported_files = Arc::clone(&imported_files);
+                        // cloning a lexeme shares its text, which the closure can own
+                        let imported_path: Lexeme = head.curr.lexeme.clone();
+                        let source_map = Arc::clone(&source_map);
 
                         thread_handles.push_back(thread::spawn(move || -> ASTs {
-                            parse(&imported_path, imported_files)
+                            parse(&imported_path, source_map)
                         }));
                     }
                     Err(e) => {
-                        utils::print_error(curr_file_name, "import", e);
+                        utils::print_error(head.source, "import", e);
                         head.synchronize();
                     }
                 }
 
                 head.advance();
                 if let Err(e) = head.require_current_is(TokenType::Semicolon) {
-                    utils::print_error(curr_file_name, &head.prev.lexeme, e);
+                    utils::print_error(head.source, &head.prev.lexeme, e);
                     head.synchronize();
                     continue;
                 }
@@ -122,21 +150,21 @@ fn parse_global_stmt(
             TokenType::Fn => match parse_function_definition(head) {
                 Ok(func_ast) => ast.fns.push(func_ast),
                 Err(e) => {
-                    utils::print_error(curr_file_name, &head.prev.lexeme, e);
+                    utils::print_error(head.source, &head.prev.lexeme, e);
                     head.synchronize();
                 }
             },
             TokenType::Enum => match parse_enum_definition(head) {
                 Ok(enum_ast) => ast.enums.push(enum_ast),
                 Err(e) => {
-                    utils::print_error(curr_file_name, &head.prev.lexeme, e);
+                    utils::print_error(head.source, &head.prev.lexeme, e);
                     head.synchronize();
                 }
             },
             TokenType::Struct => match parse_struct_definition(head) {
                 Ok(struct_ast) => ast.structs.push(struct_ast),
                 Err(e) => {
-                    utils::print_error(curr_file_name, &head.prev.lexeme, e);
+                    utils::print_error(head.source, &head.prev.lexeme, e);
                     head.synchronize();
                 }
             },
@@ -147,6 +175,7 @@ fn parse_global_stmt(
 
 fn parse_function_definition(head: &mut ParserHead) -> Result<Function, ParseError> {
     let mut function: Function;
+    let docs = std::mem::take(&mut head.docs);
 
     // fn -> fn_name
     head.advance();
@@ -160,10 +189,11 @@ fn parse_function_definition(head: &mut ParserHead) -> Result<Function, ParseErr
         }
         _ => {
             return Err(ParseError::InvalidFnName {
-                name: std::mem::take(&mut head.curr),
+                name: Box::new(std::mem::take(&mut head.curr)),
             })
         }
     }
+    function.docs = docs;
 
     // fn_name -> (
     head.advance();
@@ -184,7 +214,7 @@ fn parse_function_definition(head: &mut ParserHead) -> Result<Function, ParseErr
             }
             _ => {
                 return Err(ParseError::UnexpectedToken {
-                    token: std::mem::take(&mut head.curr),
+                    token: Box::new(std::mem::take(&mut head.curr)),
                     expected: TokenType::RightParen,
                     msg: Some(String::from(
                         "After a function argument there should have been either a `,` or a `)`.",
@@ -220,7 +250,7 @@ fn parse_function_definition(head: &mut ParserHead) -> Result<Function, ParseErr
         TokenType::Semicolon => None,
         _ => {
             return Err(ParseError::InvalidFnBody {
-                body: std::mem::take(&mut head.curr),
+                body: Box::new(std::mem::take(&mut head.curr)),
             })
         }
     };
@@ -229,6 +259,8 @@ fn parse_function_definition(head: &mut ParserHead) -> Result<Function, ParseErr
 }
 
 fn parse_enum_definition(head: &mut ParserHead) -> Result<Enum, ParseError> {
+    let docs = std::mem::take(&mut head.docs);
+
     // enum -> enum_name
     head.advance();
 
@@ -237,12 +269,22 @@ fn parse_enum_definition(head: &mut ParserHead) -> Result<Enum, ParseError> {
 
     let enum_name = std::mem::take(&mut head.prev);
 
+    // enum_name -> : backing_type
+    let backing: Option<DataType> = match head.curr.ttype {
+        TokenType::Colon => {
+            head.advance();
+            Some(head.parse_datatype()?)
+        }
+        _ => None,
+    };
+
     head.require_current_is(TokenType::LeftBrace)?;
     head.advance();
 
-    let mut variants: HashMap<Box<Token>, Option<DataType>> = HashMap::new();
+    let mut variants: Vec<Variant> = vec![];
     while !matches!(head.curr.ttype, TokenType::RightBrace) {
         head.require_current_is(TokenType::Identifier)?;
+        let variant_docs = std::mem::take(&mut head.docs);
         head.advance();
 
         let variant_name = std::mem::take(&mut head.prev);
@@ -259,7 +301,20 @@ fn parse_enum_definition(head: &mut ParserHead) -> Result<Enum, ParseError> {
             _ => None,
         };
 
-        variants.insert(Box::from(*variant_name), variant_type);
+        let value: Option<Expression> = match head.curr.ttype {
+            TokenType::Equal => {
+                head.advance();
+                Some(*expression_parser::unary(head)?)
+            }
+            _ => None,
+        };
+
+        variants.push(Variant {
+            name: variant_name,
+            datatype: variant_type,
+            value,
+            docs: variant_docs,
+        });
 
         match head.curr.ttype {
             TokenType::RightBrace => break,
@@ -268,7 +323,7 @@ fn parse_enum_definition(head: &mut ParserHead) -> Result<Enum, ParseError> {
             }
             _ => {
                 return Err(ParseError::UnexpectedToken {
-                    token: std::mem::take(&mut head.curr),
+                    token: Box::new(std::mem::take(&mut head.curr)),
                     expected: TokenType::RightParen,
                     msg: Some(String::from(
                         "After an enum variant there should have been either a `,` or a `}`.",
@@ -281,10 +336,12 @@ fn parse_enum_definition(head: &mut ParserHead) -> Result<Enum, ParseError> {
     head.require_current_is(TokenType::RightBrace)?;
     head.advance();
 
-    Ok(Enum::new(Box::from(*enum_name), variants))
+    Ok(Enum::new(enum_name, backing, variants, docs))
 }
 
 fn parse_struct_definition(head: &mut ParserHead) -> Result<Struct, ParseError> {
+    let docs = std::mem::take(&mut head.docs);
+
     // struct -> struct_name
     head.advance();
 
@@ -296,9 +353,10 @@ fn parse_struct_definition(head: &mut ParserHead) -> Result<Struct, ParseError>
     head.require_current_is(TokenType::LeftBrace)?;
     head.advance();
 
-    let mut fields: Vec<(Box<Token>, DataType)> = vec![];
+    let mut fields: Vec<Field> = vec![];
     while !matches!(head.curr.ttype, TokenType::RightBrace) {
         head.require_current_is(TokenType::Identifier)?;
+        let field_docs = std::mem::take(&mut head.docs);
         head.advance();
 
         let field_name = std::mem::take(&mut head.prev);
@@ -308,7 +366,11 @@ fn parse_struct_definition(head: &mut ParserHead) -> Result<Struct, ParseError>
 
         let field_type: DataType = head.parse_datatype()?;
 
-        fields.push((field_name, field_type));
+        fields.push(Field {
+            name: field_name,
+            datatype: field_type,
+            docs: field_docs,
+        });
 
         match head.curr.ttype {
             TokenType::RightBrace => break,
@@ -317,7 +379,7 @@ fn parse_struct_definition(head: &mut ParserHead) -> Result<Struct, ParseError>
             }
             _ => {
                 return Err(ParseError::UnexpectedToken {
-                    token: std::mem::take(&mut head.curr),
+                    token: Box::new(std::mem::take(&mut head.curr)),
                     expected: TokenType::RightParen,
                     msg: Some(String::from(
                         "After a struct field there should have been either a `,` or a `}`.",
@@ -330,5 +392,5 @@ fn parse_struct_definition(head: &mut ParserHead) -> Result<Struct, ParseError>
     head.require_current_is(TokenType::RightBrace)?;
     head.advance();
 
-    Ok(Struct::new(struct_name, fields))
+    Ok(Struct::new(struct_name, fields, docs))
 }
@@ -4,31 +4,97 @@ use crate::tokens::{
     error::ParseError, source::SourceFile, token::Token, token_type::TokenType, tokenizer,
 };
 
-use super::ast::datatypes::DataType;
+use super::{ast::datatypes::DataType, utils};
 
 pub struct ParserHead<'a> {
-    pub curr: Box<Token>,
-    pub prev: Box<Token>,
+    pub curr: Token,
+    pub prev: Token,
     pub source: &'a mut SourceFile,
+
+    /// Doc comments written right before `curr`.
+    pub docs: Vec<Token>,
+
+    /// Whether a name followed by `{` starts a struct literal, it doesn't in
+    /// the condition of an `if`, `while` or `match` where the `{` opens the body.
+    pub struct_literals: bool,
+
+    /// Problems that don't stop the parse, they're reported once the whole
+    /// file is parsed.
+    pub diagnostics: Vec<ParseError>,
 }
 
 impl<'a> ParserHead<'a> {
-    pub fn new(curr: Box<Token>, prev: Box<Token>, source: &'a mut SourceFile) -> Self {
-        Self { curr, prev, source }
+    pub fn new(curr: Token, prev: Token, source: &'a mut SourceFile) -> Self {
+        let mut head = Self {
+            curr: Token::new(),
+            prev,
+            source,
+            docs: vec![],
+            struct_literals: true,
+            diagnostics: vec![],
+        };
+
+        head.curr = head.next_token(curr);
+        head
     }
 
     #[inline]
     pub fn advance(&mut self) -> &Token {
-        self.prev = mem::replace(&mut self.curr, tokenizer::get_token(&mut self.source));
+        let token = tokenizer::get_token(self.source);
+        let token = self.next_token(token);
+
+        self.prev = mem::replace(&mut self.curr, token);
         &self.curr
     }
 
+    /// Lexical errors are reported as soon as they're lexed and doc comments
+    /// are set aside in `docs`, the grammar never gets to see either.
+    fn next_token(&mut self, mut token: Token) -> Token {
+        self.docs.clear();
+
+        loop {
+            match token.ttype {
+                TokenType::Error(_) => utils::print_lex_error(self.source, &token),
+                TokenType::DocComment => self.docs.push(token),
+                _ => return token,
+            }
+
+            token = tokenizer::get_token(self.source);
+        }
+    }
+
+    /// 1-based line where `curr` starts.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.source.line_column(self.curr.span.start).0
+    }
+
+    /// 0-based column, in characters, where `curr` starts.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.source.line_column(self.curr.span.start).1
+    }
+
+    /// Runs `parse` with struct literals allowed or not, restoring the
+    /// previous setting afterwards.
+    pub fn with_struct_literals<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let previous: bool = mem::replace(&mut self.struct_literals, allowed);
+        let res = parse(self);
+        self.struct_literals = previous;
+
+        res
+    }
+
     pub fn require_current_is(&mut self, expected: TokenType) -> Result<(), ParseError> {
-        if (*self.curr).ttype == expected {
+        if self.curr.ttype == expected {
             Ok(())
         } else {
             Err(ParseError::UnexpectedToken {
-                token: std::mem::take(&mut self.curr),
+                token: Box::new(std::mem::take(&mut self.curr)),
                 expected,
                 msg: None,
             })
@@ -51,13 +117,14 @@ impl<'a> ParserHead<'a> {
             TokenType::F64 => Ok(self.handle_pointer_datatype(DataType::F64)),
             TokenType::Bool => Ok(self.handle_pointer_datatype(DataType::Bool)),
             TokenType::StringType => Ok(self.handle_pointer_datatype(DataType::String)),
+            TokenType::CharType => Ok(self.handle_pointer_datatype(DataType::Char)),
             TokenType::Void => {
                 let datatype = self.handle_pointer_datatype(DataType::Void);
                 if matches!(datatype, DataType::Pointer(_)) {
                     Ok(datatype)
                 } else {
                     Err(ParseError::InvalidDataType {
-                        token: std::mem::take(&mut self.curr),
+                        token: Box::new(std::mem::take(&mut self.curr)),
                         msg: Some(
                             String::from("`void` by itself isn't a valid datatype, it should have been a void pointer `void*`."),
                         ),
@@ -73,12 +140,12 @@ impl<'a> ParserHead<'a> {
             }
             TokenType::Identifier => {
                 let datatype = DataType::Compound {
-                    name: std::mem::take(&mut self.curr),
+                    name: Box::new(std::mem::take(&mut self.curr)),
                 };
                 Ok(self.handle_pointer_datatype(datatype))
             }
             _ => Err(ParseError::InvalidDataType {
-                token: std::mem::take(&mut self.curr),
+                token: Box::new(std::mem::take(&mut self.curr)),
                 msg: None,
             }),
         }
@@ -99,7 +166,7 @@ impl<'a> ParserHead<'a> {
         }
     }
 
-    pub fn parse_argument(&mut self) -> Result<(Box<Token>, DataType), ParseError> {
+    pub fn parse_argument(&mut self) -> Result<(Token, DataType), ParseError> {
         self.require_current_is(TokenType::Identifier)?;
         let field_name = std::mem::take(&mut self.curr);
 
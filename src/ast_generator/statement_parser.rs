@@ -1,13 +1,13 @@
-use std::collections::HashMap;
-
 use crate::{
     ast_generator::expression_parser,
-    tokens::{error::ParseError, token_type::TokenType},
+    tokens::{error::ParseError, token::Token, token_type::TokenType},
 };
 
 use super::{
     ast::{
-        expressions::Expression, scopebound_statements::ScopeBoundStatement, variables::Variable,
+        expressions::Expression,
+        scopebound_statements::{MatchCase, ScopeBoundStatement},
+        variables::Variable,
     },
     expression_parser::{or_expression, parse_expression},
     parser_head::ParserHead,
@@ -25,8 +25,8 @@ pub fn parse_scopebound_statement(
         TokenType::For => parse_for(head),
         TokenType::Let => parse_variable_declaration(head),
         TokenType::LeftBrace => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
             Ok(ScopeBoundStatement::Scope {
                 line,
@@ -35,8 +35,8 @@ pub fn parse_scopebound_statement(
             })
         }
         TokenType::Defer => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
 
             let stmt: ScopeBoundStatement = parse_scopebound_statement(head)?;
@@ -53,8 +53,8 @@ pub fn parse_scopebound_statement(
             })
         }
         TokenType::Return => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
 
             let expr: Option<Box<ScopeBoundStatement>> = match head.curr.ttype {
@@ -72,8 +72,8 @@ pub fn parse_scopebound_statement(
             })
         }
         TokenType::Break => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
 
             head.require_current_is(TokenType::Semicolon)?;
@@ -82,8 +82,8 @@ pub fn parse_scopebound_statement(
             Ok(ScopeBoundStatement::Break { line, column })
         }
         TokenType::Continue => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
 
             head.require_current_is(TokenType::Semicolon)?;
@@ -102,7 +102,7 @@ pub fn parse_scope_block(head: &mut ParserHead) -> Result<Vec<ScopeBoundStatemen
         match parse_scopebound_statement(head) {
             Ok(stmt) => body.push(stmt),
             Err(e) => {
-                utils::print_error(&head.curr.found_in, &head.prev.lexeme, e);
+                utils::print_error(head.source, &head.prev.lexeme, e);
 
                 while !matches!(
                     head.curr.ttype,
@@ -125,18 +125,47 @@ pub fn parse_scope_block(head: &mut ParserHead) -> Result<Vec<ScopeBoundStatemen
 }
 
 fn parse_match(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
     head.advance();
 
-    let on: Expression = *parse_expression(head)?;
+    let on: Expression = *head.with_struct_literals(false, parse_expression)?;
 
     head.require_current_is(TokenType::LeftBrace)?;
     head.advance();
 
-    let mut cases: HashMap<Expression, Vec<ScopeBoundStatement>> = HashMap::new();
+    let mut cases: Vec<(MatchCase, Vec<ScopeBoundStatement>)> = vec![];
+    // where each arm starts, to point at both arms when one shadows the other
+    let mut starts: Vec<Token> = vec![];
     while !matches!(head.curr.ttype, TokenType::RightBrace) {
-        let case: Expression = expression_parser::match_pattern_expression(head)?;
+        let start: Token = head.curr.clone();
+        let pattern: Expression = expression_parser::match_pattern_expression(head)?;
+        let guard: Option<Expression> = match head.curr.ttype {
+            TokenType::If => {
+                head.advance();
+                Some(*or_expression(head)?)
+            }
+            _ => None,
+        };
+
+        // a guarded arm may not match, so it never shadows the ones after it
+        let shadowing = cases
+            .iter()
+            .zip(starts.iter())
+            .find(|((case, _), _)| case.guard.is_none() && case.pattern.covers(&pattern));
+        if let Some(((case, _), earlier)) = shadowing {
+            let diagnostic = match guard.is_none() && pattern.covers(&case.pattern) {
+                true => ParseError::DuplicateMatchArm {
+                    arm: Box::new(start.clone()),
+                    first: Box::new(earlier.clone()),
+                },
+                false => ParseError::UnreachableMatchArm {
+                    arm: Box::new(start.clone()),
+                    covered_by: Box::new(earlier.clone()),
+                },
+            };
+            head.diagnostics.push(diagnostic);
+        }
 
         head.require_current_is(TokenType::Arrow)?;
         head.advance();
@@ -145,7 +174,8 @@ fn parse_match(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError>
         head.advance();
 
         let value: Vec<ScopeBoundStatement> = parse_scope_block(head)?;
-        cases.insert(case, value);
+        cases.push((MatchCase { pattern, guard }, value));
+        starts.push(start);
 
         match head.curr.ttype {
             TokenType::Comma => {
@@ -156,7 +186,7 @@ fn parse_match(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError>
             }
             _ => {
                 return Err(ParseError::UnexpectedToken {
-                    token: std::mem::take(&mut head.curr),
+                    token: Box::new(std::mem::take(&mut head.curr)),
                     expected: TokenType::RightParen,
                     msg: None,
                 });
@@ -174,8 +204,8 @@ fn parse_match(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError>
 }
 
 fn parse_expression_statement(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
     let expr: Expression = *parse_expression(head)?;
 
     match head.curr.ttype {
@@ -185,7 +215,7 @@ fn parse_expression_statement(head: &mut ParserHead) -> Result<ScopeBoundStateme
         }
         TokenType::RightBrace => Ok(ScopeBoundStatement::ImplicitReturn { line, column, expr }),
         _ => Err(ParseError::InvalidExpression {
-            token: std::mem::take(&mut head.curr),
+            token: Box::new(std::mem::take(&mut head.curr)),
         }),
     }
 }
@@ -200,15 +230,15 @@ fn parse_for(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
         Ok(res)
     };
 
-    let for_line = head.curr.line;
-    let for_column = head.curr.column;
+    let for_line = head.line();
+    let for_column = head.column();
     // for -> (
     head.advance();
 
     head.require_current_is(TokenType::LeftParen)?;
     head.advance();
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
 
     let initialization: Option<Box<ScopeBoundStatement>> = match head.curr.ttype {
         TokenType::Let => Some(Box::new(parse_variable_declaration(head)?)),
@@ -250,7 +280,7 @@ fn parse_for(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
         }
         _ => {
             return Err(ParseError::LoopBodyNotFound {
-                body: std::mem::take(&mut head.curr),
+                body: Box::new(std::mem::take(&mut head.curr)),
             })
         }
     };
@@ -266,8 +296,8 @@ fn parse_for(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
 }
 
 pub fn parse_loop(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
     head.advance();
 
     match head.curr.ttype {
@@ -288,16 +318,16 @@ pub fn parse_loop(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseErr
             })
         }
         _ => Err(ParseError::LoopBodyNotFound {
-            body: std::mem::take(&mut head.curr),
+            body: Box::new(std::mem::take(&mut head.curr)),
         }),
     }
 }
 
 pub fn parse_while_loop(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
     head.advance();
-    let condition: Expression = *parse_expression(head)?;
+    let condition: Expression = *head.with_struct_literals(false, parse_expression)?;
 
     match head.curr.ttype {
         TokenType::LeftBrace => {
@@ -319,17 +349,17 @@ pub fn parse_while_loop(head: &mut ParserHead) -> Result<ScopeBoundStatement, Pa
             })
         }
         _ => Err(ParseError::LoopBodyNotFound {
-            body: std::mem::take(&mut head.curr),
+            body: Box::new(std::mem::take(&mut head.curr)),
         }),
     }
 }
 
 pub fn parse_conditional(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
 
     head.advance();
-    let condition: Expression = *or_expression(head)?;
+    let condition: Expression = *head.with_struct_literals(false, or_expression)?;
     let true_branch: Vec<ScopeBoundStatement> = parse_conditional_branch(head)?;
 
     match head.curr.ttype {
@@ -365,15 +395,15 @@ fn parse_conditional_branch(head: &mut ParserHead) -> Result<Vec<ScopeBoundState
 }
 
 fn parse_assignable_stmt(head: &mut ParserHead) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
 
     match head.curr.ttype {
         TokenType::If => parse_conditional(head),
         TokenType::Match => parse_match(head),
         TokenType::LeftBrace => {
-            let line = head.curr.line;
-            let column = head.curr.column;
+            let line = head.line();
+            let column = head.column();
             head.advance();
             Ok(ScopeBoundStatement::Scope {
                 line,
@@ -388,7 +418,7 @@ fn parse_assignable_stmt(head: &mut ParserHead) -> Result<ScopeBoundStatement, P
         | TokenType::Let
         | TokenType::Break
         | TokenType::Continue => Err(ParseError::InvalidVariableAssignment {
-            value: std::mem::take(&mut head.curr),
+            value: Box::new(std::mem::take(&mut head.curr)),
         }),
         _ => Ok(ScopeBoundStatement::Expression {
             line,
@@ -401,8 +431,8 @@ fn parse_assignable_stmt(head: &mut ParserHead) -> Result<ScopeBoundStatement, P
 pub fn parse_variable_declaration(
     head: &mut ParserHead,
 ) -> Result<ScopeBoundStatement, ParseError> {
-    let line = head.curr.line;
-    let column = head.curr.column;
+    let line = head.line();
+    let column = head.column();
 
     // let -> var_name
     head.advance();
@@ -448,7 +478,7 @@ pub fn parse_variable_declaration(
         }
         _ => {
             return Err(ParseError::InvalidVariableDeclaration {
-                token: std::mem::take(&mut head.curr),
+                token: Box::new(std::mem::take(&mut head.curr)),
             })
         }
     };
@@ -0,0 +1,61 @@
+use crate::ast_generator::ast::datatypes::DataType;
+
+use super::*;
+
+#[test]
+fn cast_binds_tighter_than_binary_operators() {
+    let found = parse(
+        "cast_binds_tighter_than_binary_operators",
+        "a + b as f32 / 2;",
+    );
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::Binary { right, .. } => match *right {
+            Expression::Binary { left, .. } => assert!(
+                matches!(*left, Expression::Cast { to: DataType::F32, ref value, .. }
+                    if matches!(**value, Expression::Name { .. }))
+            ),
+            expr => panic!("expected a binary expression, found {expr:?}"),
+        },
+        expr => panic!("expected a binary expression, found {expr:?}"),
+    }
+}
+
+#[test]
+fn cast_of_prefix_operation() {
+    let found = parse("cast_of_prefix_operation", "-a[0] as u8;");
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::Cast { value, to, .. } => {
+            assert_eq!(DataType::U8, to);
+            assert!(matches!(*value, Expression::Unary { .. }));
+        }
+        expr => panic!("expected a cast, found {expr:?}"),
+    }
+}
+
+#[test]
+fn chained_casts() {
+    let found = parse("chained_casts", "p as void* as u8*;");
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::Cast { value, to, .. } => {
+            assert_eq!(DataType::Pointer(Box::new(DataType::U8)), to);
+            assert!(matches!(*value, Expression::Cast {
+                to: DataType::Pointer(ref of), ..
+            } if **of == DataType::Void));
+        }
+        expr => panic!("expected a cast, found {expr:?}"),
+    }
+}
+
+#[test]
+fn malformed_casts() {
+    // the stars after the type belong to it
+    for content in ["a as;", "a as 3;", "a as void;", "as u8;", "a as u8 * 2;"] {
+        assert!(
+            parse("malformed_casts", content).is_err(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
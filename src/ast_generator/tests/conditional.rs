@@ -1,6 +1,5 @@
-use std::collections::HashMap;
-
 use super::*;
+use crate::ast_generator::ast::scopebound_statements::MatchCase;
 
 #[test]
 fn missing_condition() {
@@ -11,11 +10,10 @@ fn missing_condition() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 3,
+                span: Span::new(FileId::default(), 3, 4),
                 ttype: TokenType::LeftBrace,
-                lexeme: "{".to_owned(),
-                found_in: "missing_condition".to_owned()
+                lexeme: "{".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -31,11 +29,10 @@ fn missing_scope() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 8,
+                span: Span::new(FileId::default(), 8, 14),
                 ttype: TokenType::Return,
-                lexeme: "return".to_string(),
-                found_in: "missing_scope".to_string()
+                lexeme: "return".into(),
+                suffix: None,
             }),
             expected: TokenType::LeftBrace,
             msg: None
@@ -53,11 +50,10 @@ fn missing_false_branch() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 27,
+                span: Span::new(FileId::default(), 27, 27),
                 ttype: TokenType::Eof,
-                lexeme: String::new(),
-                found_in: "missing_false_branch".to_string()
+                lexeme: "".into(),
+                suffix: None,
             }),
             expected: TokenType::LeftBrace,
             msg: None
@@ -73,14 +69,17 @@ fn empty_true_branch() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Conditional {
+            line: 1,
+            column: 0,
             condition: Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 3,
+                line: 1,
+                column: 8,
+                literal: Token {
+                    span: Span::new(FileId::default(), 3, 7),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "empty_true_branch".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             },
             true_branch: vec![],
             false_branch: None
@@ -97,14 +96,17 @@ fn empty_branches() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Conditional {
+            line: 1,
+            column: 0,
             condition: Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 3,
+                line: 1,
+                column: 8,
+                literal: Token {
+                    span: Span::new(FileId::default(), 3, 7),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "empty_branches".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             },
             true_branch: vec![],
             false_branch: Some(vec![])
@@ -124,11 +126,10 @@ fn else_with_conditional_missing_condition() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 19,
+                span: Span::new(FileId::default(), 19, 20),
                 ttype: TokenType::LeftBrace,
-                lexeme: "{".to_owned(),
-                found_in: "else_with_conditional_missing_condition".to_owned()
+                lexeme: "{".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -143,25 +144,31 @@ fn else_with_conditional() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Conditional {
+            line: 1,
+            column: 0,
             condition: Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 3,
+                line: 1,
+                column: 8,
+                literal: Token {
+                    span: Span::new(FileId::default(), 3, 7),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "else_with_conditional".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             },
             true_branch: vec![],
             false_branch: Some(vec![ScopeBoundStatement::Conditional {
+                line: 1,
+                column: 16,
                 condition: Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 19,
+                    line: 1,
+                    column: 23,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 19, 22),
                         ttype: TokenType::Nil,
-                        lexeme: "nil".to_owned(),
-                        found_in: "else_with_conditional".to_owned()
-                    })
+                        lexeme: "nil".into(),
+                        suffix: None,
+                    }
                 },
                 true_branch: vec![],
                 false_branch: None
@@ -181,64 +188,84 @@ fn valid_if_stmt() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Conditional {
+            line: 1,
+            column: 0,
             condition: Expression::Binary {
+                line: 1,
+                column: 9,
                 left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 3,
-                        ttype: TokenType::Integer,
-                        lexeme: "23".to_owned(),
-                        found_in: "valid_if".to_owned(),
-                    }),
-                }),
-                operation: Box::new(Token {
                     line: 1,
                     column: 6,
-                    ttype: TokenType::EqualEqual,
-                    lexeme: "==".to_owned(),
-                    found_in: "valid_if".to_owned(),
+                    literal: Token {
+                        span: Span::new(FileId::default(), 3, 5),
+                        ttype: TokenType::Integer,
+                        lexeme: "23".into(),
+                        suffix: None,
+                    },
                 }),
+                operation: Token {
+                    span: Span::new(FileId::default(), 6, 8),
+                    ttype: TokenType::EqualEqual,
+                    lexeme: "==".into(),
+                    suffix: None,
+                },
                 right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 9,
+                    line: 1,
+                    column: 12,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 9, 11),
                         ttype: TokenType::Integer,
-                        lexeme: "23".to_owned(),
-                        found_in: "valid_if".to_owned(),
-                    }),
+                        lexeme: "23".into(),
+                        suffix: None,
+                    },
                 }),
             },
-            true_branch: vec![ScopeBoundStatement::Return(Some(Box::new(
-                ScopeBoundStatement::Expression(Expression::Literal {
-                    literal: Box::new(Token {
+            true_branch: vec![ScopeBoundStatement::Return {
+                line: 1,
+                column: 14,
+                value: Some(Box::new(ScopeBoundStatement::Expression {
+                    line: 1,
+                    column: 21,
+                    expr: Expression::Literal {
                         line: 1,
-                        column: 21,
-                        ttype: TokenType::Integer,
-                        lexeme: "42".to_owned(),
-                        found_in: "valid_if".to_owned(),
-                    })
-                })
-            )))],
-            false_branch: Some(vec![ScopeBoundStatement::Return(Some(Box::new(
-                ScopeBoundStatement::Expression(Expression::Unary {
-                    operation: Box::new(Token {
+                        column: 23,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 21, 23),
+                            ttype: TokenType::Integer,
+                            lexeme: "42".into(),
+                            suffix: None,
+                        }
+                    }
+                }))
+            }],
+            false_branch: Some(vec![ScopeBoundStatement::Return {
+                line: 1,
+                column: 34,
+                value: Some(Box::new(ScopeBoundStatement::Expression {
+                    line: 1,
+                    column: 41,
+                    expr: Expression::Unary {
                         line: 1,
                         column: 41,
-                        ttype: TokenType::Minus,
-                        lexeme: "-".to_owned(),
-                        found_in: "valid_if".to_owned()
-                    }),
-                    value: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
+                        operation: Token {
+                            span: Span::new(FileId::default(), 41, 42),
+                            ttype: TokenType::Minus,
+                            lexeme: "-".into(),
+                            suffix: None,
+                        },
+                        value: Box::new(Expression::Literal {
                             line: 1,
-                            column: 42,
-                            ttype: TokenType::Integer,
-                            lexeme: "42".to_owned(),
-                            found_in: "valid_if".to_owned(),
+                            column: 44,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 42, 44),
+                                ttype: TokenType::Integer,
+                                lexeme: "42".into(),
+                                suffix: None,
+                            }
                         })
-                    })
-                })
-            )))])
+                    }
+                }))
+            }])
         },
         found.ok().unwrap()
     );
@@ -254,66 +281,88 @@ fn valid_if_nested_condition() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Conditional {
+            line: 1,
+            column: 0,
             condition: Expression::Nested {
+                line: 1,
+                column: 14,
                 nested: Box::new(Expression::Binary {
+                    line: 1,
+                    column: 10,
                     left: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
-                            line: 1,
-                            column: 4,
-                            ttype: TokenType::Integer,
-                            lexeme: "23".to_owned(),
-                            found_in: "valid_if_grouping".to_owned(),
-                        }),
-                    }),
-                    operation: Box::new(Token {
                         line: 1,
                         column: 7,
-                        ttype: TokenType::EqualEqual,
-                        lexeme: "==".to_owned(),
-                        found_in: "valid_if_grouping".to_owned(),
+                        literal: Token {
+                            span: Span::new(FileId::default(), 4, 6),
+                            ttype: TokenType::Integer,
+                            lexeme: "23".into(),
+                            suffix: None,
+                        },
                     }),
+                    operation: Token {
+                        span: Span::new(FileId::default(), 7, 9),
+                        ttype: TokenType::EqualEqual,
+                        lexeme: "==".into(),
+                        suffix: None,
+                    },
                     right: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
-                            line: 1,
-                            column: 10,
+                        line: 1,
+                        column: 12,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 10, 12),
                             ttype: TokenType::Integer,
-                            lexeme: "23".to_owned(),
-                            found_in: "valid_if_grouping".to_owned(),
-                        }),
+                            lexeme: "23".into(),
+                            suffix: None,
+                        },
                     }),
                 })
             },
-            true_branch: vec![ScopeBoundStatement::Return(Some(Box::new(
-                ScopeBoundStatement::Expression(Expression::Literal {
-                    literal: Box::new(Token {
+            true_branch: vec![ScopeBoundStatement::Return {
+                line: 1,
+                column: 16,
+                value: Some(Box::new(ScopeBoundStatement::Expression {
+                    line: 1,
+                    column: 23,
+                    expr: Expression::Literal {
                         line: 1,
-                        column: 23,
-                        ttype: TokenType::Integer,
-                        lexeme: "42".to_owned(),
-                        found_in: "valid_if_grouping".to_owned(),
-                    })
-                })
-            )))],
-            false_branch: Some(vec![ScopeBoundStatement::Return(Some(Box::new(
-                ScopeBoundStatement::Expression(Expression::Unary {
-                    operation: Box::new(Token {
+                        column: 25,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 23, 25),
+                            ttype: TokenType::Integer,
+                            lexeme: "42".into(),
+                            suffix: None,
+                        }
+                    }
+                }))
+            }],
+            false_branch: Some(vec![ScopeBoundStatement::Return {
+                line: 1,
+                column: 36,
+                value: Some(Box::new(ScopeBoundStatement::Expression {
+                    line: 1,
+                    column: 43,
+                    expr: Expression::Unary {
                         line: 1,
                         column: 43,
-                        ttype: TokenType::Minus,
-                        lexeme: "-".to_owned(),
-                        found_in: "valid_if_grouping".to_owned()
-                    }),
-                    value: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
+                        operation: Token {
+                            span: Span::new(FileId::default(), 43, 44),
+                            ttype: TokenType::Minus,
+                            lexeme: "-".into(),
+                            suffix: None,
+                        },
+                        value: Box::new(Expression::Literal {
                             line: 1,
-                            column: 44,
-                            ttype: TokenType::Integer,
-                            lexeme: "42".to_owned(),
-                            found_in: "valid_if_grouping".to_owned(),
+                            column: 46,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 44, 46),
+                                ttype: TokenType::Integer,
+                                lexeme: "42".into(),
+                                suffix: None,
+                            }
                         })
-                    })
-                })
-            )))])
+                    }
+                }))
+            }])
         },
         found.ok().unwrap()
     );
@@ -329,84 +378,108 @@ fn valid_match() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Match {
+            line: 1,
+            column: 0,
             on: Expression::Binary {
+                line: 1,
+                column: 12,
                 left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 6,
-                        ttype: TokenType::Integer,
-                        lexeme: "23".to_owned(),
-                        found_in: "valid_match".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
                     line: 1,
                     column: 9,
-                    ttype: TokenType::NotEqual,
-                    lexeme: "!=".to_owned(),
-                    found_in: "valid_match".to_owned()
+                    literal: Token {
+                        span: Span::new(FileId::default(), 6, 8),
+                        ttype: TokenType::Integer,
+                        lexeme: "23".into(),
+                        suffix: None,
+                    }
                 }),
+                operation: Token {
+                    span: Span::new(FileId::default(), 9, 11),
+                    ttype: TokenType::NotEqual,
+                    lexeme: "!=".into(),
+                    suffix: None,
+                },
                 right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 12,
+                    line: 1,
+                    column: 15,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 12, 14),
                         ttype: TokenType::Integer,
-                        lexeme: "23".to_owned(),
-                        found_in: "valid_match".to_owned()
-                    })
+                        lexeme: "23".into(),
+                        suffix: None,
+                    }
                 })
             },
-            cases: HashMap::from([
+            cases: vec![
                 (
-                    Expression::Literal {
-                        literal: Box::new(Token {
+                    MatchCase {
+                        pattern: Expression::Literal {
                             line: 1,
-                            column: 17,
-                            ttype: TokenType::True,
-                            lexeme: "true".to_owned(),
-                            found_in: "valid_match".to_owned()
-                        })
+                            column: 22,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 17, 21),
+                                ttype: TokenType::True,
+                                lexeme: "true".into(),
+                                suffix: None,
+                            }
+                        },
+                        guard: None,
                     },
-                    vec![ScopeBoundStatement::ImplicitReturn(Expression::Literal {
-                        literal: Box::new(Token {
+                    vec![ScopeBoundStatement::ImplicitReturn {
+                        line: 1,
+                        column: 27,
+                        expr: Expression::Literal {
                             line: 1,
-                            column: 27,
-                            ttype: TokenType::Integer,
-                            lexeme: "42".to_owned(),
-                            found_in: "valid_match".to_owned()
-                        })
-                    })]
+                            column: 30,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 27, 29),
+                                ttype: TokenType::Integer,
+                                lexeme: "42".into(),
+                                suffix: None,
+                            }
+                        }
+                    }]
                 ),
                 (
-                    Expression::Literal {
-                        literal: Box::new(Token {
+                    MatchCase {
+                        pattern: Expression::Literal {
                             line: 1,
-                            column: 33,
-                            ttype: TokenType::False,
-                            lexeme: "false".to_owned(),
-                            found_in: "valid_match".to_owned()
-                        })
+                            column: 39,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 33, 38),
+                                ttype: TokenType::False,
+                                lexeme: "false".into(),
+                                suffix: None,
+                            }
+                        },
+                        guard: None,
                     },
-                    vec![ScopeBoundStatement::ImplicitReturn(Expression::Unary {
-                        operation: Box::new(Token {
+                    vec![ScopeBoundStatement::ImplicitReturn {
+                        line: 1,
+                        column: 44,
+                        expr: Expression::Unary {
                             line: 1,
                             column: 44,
-                            ttype: TokenType::Minus,
-                            lexeme: "-".to_owned(),
-                            found_in: "valid_match".to_owned()
-                        }),
-                        value: Box::new(Expression::Literal {
-                            literal: Box::new(Token {
+                            operation: Token {
+                                span: Span::new(FileId::default(), 44, 45),
+                                ttype: TokenType::Minus,
+                                lexeme: "-".into(),
+                                suffix: None,
+                            },
+                            value: Box::new(Expression::Literal {
                                 line: 1,
-                                column: 45,
-                                ttype: TokenType::Integer,
-                                lexeme: "42".to_owned(),
-                                found_in: "valid_match".to_owned()
+                                column: 48,
+                                literal: Token {
+                                    span: Span::new(FileId::default(), 45, 47),
+                                    ttype: TokenType::Integer,
+                                    lexeme: "42".into(),
+                                    suffix: None,
+                                }
                             })
-                        })
-                    })]
+                        }
+                    }]
                 )
-            ])
+            ]
         },
         found.ok().unwrap()
     );
@@ -422,86 +495,112 @@ fn valid_match_nested_condition() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::Match {
+            line: 1,
+            column: 0,
             on: Expression::Nested {
+                line: 1,
+                column: 17,
                 nested: Box::new(Expression::Binary {
+                    line: 1,
+                    column: 13,
                     left: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
-                            line: 1,
-                            column: 7,
-                            ttype: TokenType::Integer,
-                            lexeme: "23".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        })
-                    }),
-                    operation: Box::new(Token {
                         line: 1,
                         column: 10,
-                        ttype: TokenType::NotEqual,
-                        lexeme: "!=".to_owned(),
-                        found_in: "valid_match_nested_condition".to_owned()
+                        literal: Token {
+                            span: Span::new(FileId::default(), 7, 9),
+                            ttype: TokenType::Integer,
+                            lexeme: "23".into(),
+                            suffix: None,
+                        }
                     }),
+                    operation: Token {
+                        span: Span::new(FileId::default(), 10, 12),
+                        ttype: TokenType::NotEqual,
+                        lexeme: "!=".into(),
+                        suffix: None,
+                    },
                     right: Box::new(Expression::Literal {
-                        literal: Box::new(Token {
-                            line: 1,
-                            column: 13,
+                        line: 1,
+                        column: 15,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 13, 15),
                             ttype: TokenType::Integer,
-                            lexeme: "23".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        })
+                            lexeme: "23".into(),
+                            suffix: None,
+                        }
                     })
                 })
             },
-            cases: HashMap::from([
+            cases: vec![
                 (
-                    Expression::Literal {
-                        literal: Box::new(Token {
+                    MatchCase {
+                        pattern: Expression::Literal {
                             line: 1,
-                            column: 19,
-                            ttype: TokenType::True,
-                            lexeme: "true".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        })
+                            column: 24,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 19, 23),
+                                ttype: TokenType::True,
+                                lexeme: "true".into(),
+                                suffix: None,
+                            }
+                        },
+                        guard: None,
                     },
-                    vec![ScopeBoundStatement::ImplicitReturn(Expression::Literal {
-                        literal: Box::new(Token {
+                    vec![ScopeBoundStatement::ImplicitReturn {
+                        line: 1,
+                        column: 29,
+                        expr: Expression::Literal {
                             line: 1,
-                            column: 29,
-                            ttype: TokenType::Integer,
-                            lexeme: "42".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        })
-                    })]
+                            column: 32,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 29, 31),
+                                ttype: TokenType::Integer,
+                                lexeme: "42".into(),
+                                suffix: None,
+                            }
+                        }
+                    }]
                 ),
                 (
-                    Expression::Literal {
-                        literal: Box::new(Token {
+                    MatchCase {
+                        pattern: Expression::Literal {
                             line: 1,
-                            column: 35,
-                            ttype: TokenType::False,
-                            lexeme: "false".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        })
+                            column: 41,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 35, 40),
+                                ttype: TokenType::False,
+                                lexeme: "false".into(),
+                                suffix: None,
+                            }
+                        },
+                        guard: None,
                     },
-                    vec![ScopeBoundStatement::ImplicitReturn(Expression::Unary {
-                        operation: Box::new(Token {
+                    vec![ScopeBoundStatement::ImplicitReturn {
+                        line: 1,
+                        column: 46,
+                        expr: Expression::Unary {
                             line: 1,
                             column: 46,
-                            ttype: TokenType::Minus,
-                            lexeme: "-".to_owned(),
-                            found_in: "valid_match_nested_condition".to_owned()
-                        }),
-                        value: Box::new(Expression::Literal {
-                            literal: Box::new(Token {
+                            operation: Token {
+                                span: Span::new(FileId::default(), 46, 47),
+                                ttype: TokenType::Minus,
+                                lexeme: "-".into(),
+                                suffix: None,
+                            },
+                            value: Box::new(Expression::Literal {
                                 line: 1,
-                                column: 47,
-                                ttype: TokenType::Integer,
-                                lexeme: "42".to_owned(),
-                                found_in: "valid_match_nested_condition".to_owned()
+                                column: 50,
+                                literal: Token {
+                                    span: Span::new(FileId::default(), 47, 49),
+                                    ttype: TokenType::Integer,
+                                    lexeme: "42".into(),
+                                    suffix: None,
+                                }
                             })
-                        })
-                    })]
+                        }
+                    }]
                 )
-            ])
+            ]
         },
         found.ok().unwrap()
     );
@@ -518,11 +617,10 @@ fn match_without_arrow() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 22,
+                span: Span::new(FileId::default(), 22, 23),
                 ttype: TokenType::LeftBrace,
-                lexeme: "{".to_owned(),
-                found_in: "valid_match_nested_condition".to_owned()
+                lexeme: "{".into(),
+                suffix: None,
             }),
             expected: TokenType::Arrow,
             msg: None,
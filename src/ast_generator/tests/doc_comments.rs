@@ -0,0 +1,54 @@
+use super::*;
+
+fn doc_lines(docs: &[Token]) -> Vec<&str> {
+    docs.iter().map(|doc| doc.lexeme.as_str()).collect()
+}
+
+#[test]
+fn doc_comments_attach_to_items() {
+    let ast = parse_items(
+        "doc_comments_attach_to_items",
+        "#: A point in space.
+#: Immutable.
+struct Point {
+    #: Horizontal axis.
+    x: i32,
+    y: i32,
+}
+
+#: Either a number or nothing.
+enum Maybe {
+    #: Holds the number.
+    Some(i32),
+    None,
+}
+
+#: Entry point.
+fn main() {
+    #: not attached to anything
+    let x = 1;
+}
+
+fn undocumented() {}
+",
+    );
+
+    let point = &ast.structs[0];
+    assert_eq!(
+        vec!["A point in space.", "Immutable."],
+        doc_lines(&point.docs)
+    );
+    assert_eq!(vec!["Horizontal axis."], doc_lines(&point.fields[0].docs));
+    assert!(point.fields[1].docs.is_empty());
+
+    let maybe = &ast.enums[0];
+    assert_eq!(vec!["Either a number or nothing."], doc_lines(&maybe.docs));
+    assert_eq!(
+        vec!["Holds the number."],
+        doc_lines(&maybe.variants[0].docs)
+    );
+    assert!(maybe.variants[1].docs.is_empty());
+
+    assert_eq!(vec!["Entry point."], doc_lines(&ast.fns[0].docs));
+    assert!(ast.fns[1].docs.is_empty());
+}
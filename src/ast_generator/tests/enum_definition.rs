@@ -0,0 +1,74 @@
+use crate::ast_generator::ast::{datatypes::DataType, enums::Enum};
+
+use super::*;
+
+fn parse_enums(file_name: &str, content: &str) -> Vec<Enum> {
+    parse_items(file_name, content).enums
+}
+
+fn value_of(value: &Option<Expression>) -> Option<String> {
+    match value {
+        None => None,
+        Some(Expression::Literal { literal, .. }) => Some(literal.lexeme.to_string()),
+        Some(Expression::Unary {
+            operation, value, ..
+        }) => match &**value {
+            Expression::Literal { literal, .. } => {
+                Some(format!("{}{}", operation.lexeme, literal.lexeme))
+            }
+            value => panic!("expected a literal, found {value:?}"),
+        },
+        Some(value) => panic!("expected a literal, found {value:?}"),
+    }
+}
+
+#[test]
+fn variants_keep_declaration_order() {
+    let enums = parse_enums(
+        "variants_keep_declaration_order",
+        "enum Shape { Square(f32), Circle(f32), Point, Line(f32), Dot }",
+    );
+
+    let names: Vec<&str> = enums[0]
+        .variants
+        .iter()
+        .map(|variant| variant.name.lexeme.as_str())
+        .collect();
+    assert_eq!(vec!["Square", "Circle", "Point", "Line", "Dot"], names);
+    assert_eq!(None, enums[0].backing);
+}
+
+#[test]
+fn explicit_discriminants() {
+    let enums = parse_enums(
+        "explicit_discriminants",
+        "enum Color: u8 { Red = 3, Green, Blue = 10 }\nenum Offset: i16 { Back = -1, Here }",
+    );
+
+    assert_eq!(Some(DataType::U8), enums[0].backing);
+    let values: Vec<Option<String>> = enums[0]
+        .variants
+        .iter()
+        .map(|variant| value_of(&variant.value))
+        .collect();
+    assert_eq!(
+        vec![Some("3".to_owned()), None, Some("10".to_owned())],
+        values
+    );
+
+    assert_eq!(Some(DataType::I16), enums[1].backing);
+    assert_eq!(Some("-1".to_owned()), value_of(&enums[1].variants[0].value));
+}
+
+#[test]
+fn malformed_enum_definitions() {
+    for (file_name, content) in [
+        ("malformed_enum_definitions_backing", "enum Color: { Red }"),
+        ("malformed_enum_definitions_value", "enum Color { Red = }"),
+    ] {
+        assert!(
+            parse_enums(file_name, content).is_empty(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
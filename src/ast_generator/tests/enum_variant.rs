@@ -0,0 +1,113 @@
+use super::*;
+
+fn patterns_of(stmt: ScopeBoundStatement) -> Vec<Expression> {
+    match stmt {
+        ScopeBoundStatement::Match { cases, .. } => {
+            cases.into_iter().map(|(case, _)| case.pattern).collect()
+        }
+        stmt => panic!("expected a match, found {stmt:?}"),
+    }
+}
+
+#[test]
+fn variant_construction() {
+    let found = parse("variant_construction", "Shape::Circle(2.0);");
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::EnumVariant {
+            enumeration,
+            variant,
+            payload: Some(payload),
+            ..
+        } => {
+            assert_eq!("Shape", enumeration.lexeme);
+            assert_eq!("Circle", variant.lexeme);
+            assert!(
+                matches!(*payload, Expression::Literal { ref literal, .. } if literal.lexeme == "2.0")
+            );
+        }
+        expr => panic!("expected an enum variant, found {expr:?}"),
+    }
+}
+
+#[test]
+fn unit_variant() {
+    let found = parse("unit_variant", "Shape::Empty;");
+
+    assert!(matches!(
+        expression_of(found.ok().unwrap()),
+        Expression::EnumVariant { ref variant, payload: None, .. } if variant.lexeme == "Empty"
+    ));
+}
+
+#[test]
+fn destructuring_pattern() {
+    let found = parse(
+        "destructuring_pattern",
+        "match s { Shape::Circle(r) -> { r; }, }",
+    );
+    let patterns = patterns_of(found.ok().unwrap());
+
+    match &patterns[..] {
+        [Expression::EnumVariant {
+            variant,
+            payload: Some(payload),
+            ..
+        }] => {
+            assert_eq!("Circle", variant.lexeme);
+            assert!(matches!(**payload, Expression::Name { ref name, .. } if name.lexeme == "r"));
+        }
+        patterns => panic!("expected a single enum variant pattern, found {patterns:?}"),
+    }
+}
+
+#[test]
+fn nested_pattern() {
+    let found = parse(
+        "nested_pattern",
+        "match s { Shape::Wrap(Option::Some(1..3)) -> { 1; }, Shape::Wrap(_) -> { 2; } }",
+    );
+    let patterns = patterns_of(found.ok().unwrap());
+    assert_eq!(2, patterns.len());
+
+    let nested = patterns
+        .iter()
+        .find_map(|pattern| match pattern {
+            Expression::EnumVariant {
+                payload: Some(payload),
+                ..
+            } => match **payload {
+                Expression::EnumVariant {
+                    ref enumeration,
+                    payload: Some(ref payload),
+                    ..
+                } if enumeration.lexeme == "Option" => Some(payload),
+                _ => None,
+            },
+            _ => None,
+        })
+        .expect("the nested pattern should have been found");
+
+    assert!(matches!(
+        **nested,
+        Expression::Sequence {
+            start: 1,
+            end: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn malformed_variant() {
+    for content in [
+        "Shape::;",
+        "Shape::Circle(;",
+        "match s { Shape::Circle(r -> { r; } }",
+    ] {
+        assert!(
+            parse("malformed_variant", content).is_err(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+fn lexeme_of(expr: &Expression) -> &str {
+    match expr {
+        Expression::Name { name, .. } => name.lexeme.as_str(),
+        Expression::Literal { literal, .. } => literal.lexeme.as_str(),
+        expr => panic!("expected a name or a literal, found {expr:?}"),
+    }
+}
+
+#[test]
+fn index_chain() {
+    let found = parse("index_chain", "a.b[i][j + 1];");
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::Index { from, index, .. } => {
+            assert!(matches!(*index, Expression::Binary { .. }));
+
+            match *from {
+                Expression::Index { from, index, .. } => {
+                    assert_eq!("i", lexeme_of(&index));
+                    assert!(
+                        matches!(*from, Expression::GetField { ref get, .. } if get.lexeme == "b")
+                    );
+                }
+                expr => panic!("expected an index expression, found {expr:?}"),
+            }
+        }
+        expr => panic!("expected an index expression, found {expr:?}"),
+    }
+}
+
+#[test]
+fn field_of_index() {
+    let found = parse("field_of_index", "a[0].b;");
+
+    match expression_of(found.ok().unwrap()) {
+        Expression::GetField { from, get, .. } => {
+            assert_eq!("b", get.lexeme);
+            assert!(matches!(*from, Expression::Index { .. }));
+        }
+        expr => panic!("expected a get field expression, found {expr:?}"),
+    }
+}
+
+#[test]
+fn slices() {
+    let expected = vec![
+        ("a[1..4];", Some("1"), Some("4"), false),
+        ("a[1..=4];", Some("1"), Some("4"), true),
+        ("a[1..];", Some("1"), None, false),
+        ("a[..4];", None, Some("4"), false),
+        ("a[..=4];", None, Some("4"), true),
+        ("a[..];", None, None, false),
+    ];
+
+    for (content, expected_start, expected_end, expected_inclusive) in expected {
+        let found = parse("slices", content);
+
+        match expression_of(found.ok().unwrap()) {
+            Expression::Slice {
+                from,
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                assert_eq!("a", lexeme_of(&from), "{content}");
+                assert_eq!(expected_start, start.as_deref().map(lexeme_of), "{content}");
+                assert_eq!(expected_end, end.as_deref().map(lexeme_of), "{content}");
+                assert_eq!(expected_inclusive, inclusive, "{content}");
+            }
+            expr => panic!("expected a slice expression in `{content}`, found {expr:?}"),
+        }
+    }
+}
+
+#[test]
+fn assign_to_index_and_slice() {
+    for content in ["a[i] = 1;", "a[0] += 1;", "a[1..3] = b;"] {
+        let found = parse("assign_to_index_and_slice", content);
+
+        match expression_of(found.ok().unwrap()) {
+            Expression::Binary { left, .. } => assert!(matches!(
+                *left,
+                Expression::Index { .. } | Expression::Slice { .. }
+            )),
+            expr => panic!("expected an assignment in `{content}`, found {expr:?}"),
+        }
+    }
+}
+
+#[test]
+fn malformed_index() {
+    for content in ["a[1;", "a[..=];", "a[];"] {
+        assert!(
+            parse("malformed_index", content).is_err(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
+
+#[test]
+fn index_span() {
+    for (content, expected) in [("a.b[i + 1];", 3..10), ("a[1..][..=2];", 6..12)] {
+        let span = match expression_of(parse("index_span", content).ok().unwrap()) {
+            Expression::Index { span, .. } | Expression::Slice { span, .. } => span,
+            expr => panic!("expected an index or a slice in `{content}`, found {expr:?}"),
+        };
+
+        assert_eq!(expected, span.start..span.end, "{content}");
+    }
+}
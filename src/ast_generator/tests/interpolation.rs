@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn interpolation_with_single_hole() {
+    let found = parse("interpolation_with_single_hole", "\"x = {x}!\";");
+
+    assert!(found.is_ok());
+    assert_eq!(
+        ScopeBoundStatement::Expression {
+            line: 1,
+            column: 0,
+            expr: Expression::Interpolation {
+                line: 1,
+                column: 0,
+                strings: vec![
+                    Token {
+                        span: Span::new(FileId::default(), 0, 6),
+                        ttype: TokenType::StringHead,
+                        lexeme: "x = ".into(),
+                        suffix: None,
+                    },
+                    Token {
+                        span: Span::new(FileId::default(), 7, 10),
+                        ttype: TokenType::StringTail,
+                        lexeme: "!".into(),
+                        suffix: None,
+                    },
+                ],
+                holes: vec![Expression::Name {
+                    line: 1,
+                    column: 7,
+                    name: Token {
+                        span: Span::new(FileId::default(), 6, 7),
+                        ttype: TokenType::Identifier,
+                        lexeme: "x".into(),
+                        suffix: None,
+                    },
+                }],
+            },
+        },
+        found.ok().unwrap()
+    );
+}
+
+#[test]
+fn interpolation_with_nested_string() {
+    let found = parse("interpolation_with_nested_string", "\"a{ \"b{c}\" }d{e}\";");
+
+    assert!(found.is_ok());
+    match found.ok().unwrap() {
+        ScopeBoundStatement::Expression {
+            expr: Expression::Interpolation { strings, holes, .. },
+            ..
+        } => {
+            assert_eq!(
+                vec!["a", "d", ""],
+                strings
+                    .iter()
+                    .map(|string| string.lexeme.as_str())
+                    .collect::<Vec<&str>>()
+            );
+            assert!(matches!(holes[0], Expression::Interpolation { .. }));
+            assert!(matches!(holes[1], Expression::Name { .. }));
+        }
+        stmt => panic!("expected an interpolation expression, found {stmt:?}"),
+    }
+}
+
+#[test]
+fn interpolation_with_empty_hole() {
+    let found = parse("interpolation_with_empty_hole", "\"a{}b\";");
+
+    assert!(found.is_err());
+    assert_eq!(
+        ParseError::InvalidExpression {
+            token: Box::new(Token {
+                span: Span::new(FileId::default(), 3, 6),
+                ttype: TokenType::StringTail,
+                lexeme: "b".into(),
+                suffix: None,
+            })
+        },
+        found.err().unwrap()
+    );
+}
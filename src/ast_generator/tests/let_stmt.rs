@@ -1,7 +1,5 @@
-use std::collections::HashMap;
-
 use super::*;
-use crate::ast_generator::ast::variables::{DataType, Variable};
+use crate::ast_generator::ast::{datatypes::DataType, variables::Variable};
 
 #[test]
 fn let_without_value() {
@@ -11,11 +9,10 @@ fn let_without_value() {
     assert_eq!(
         ParseError::InvalidVariableDeclaration {
             token: Box::new(Token {
-                line: 1,
-                column: 10,
+                span: Span::new(FileId::default(), 10, 11),
                 ttype: TokenType::Semicolon,
-                lexeme: ";".to_string(),
-                found_in: "let_without_value".to_string()
+                lexeme: ";".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -28,25 +25,33 @@ fn let_builtin_type() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::DontCare,
-                lexeme: "_".to_owned(),
-                found_in: "let_builtin_type".to_owned()
-            }),
-            Some(crate::ast_generator::ast::variables::DataType::U8),
-            Box::new(ScopeBoundStatement::Expression(Expression::Literal {
-                literal: Box::new(Token {
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 5),
+                    ttype: TokenType::DontCare,
+                    lexeme: "_".into(),
+                    suffix: None,
+                },
+                Some(crate::ast_generator::ast::datatypes::DataType::U8),
+                Box::new(ScopeBoundStatement::Expression {
                     line: 1,
                     column: 13,
-                    ttype: TokenType::Integer,
-                    lexeme: "10".to_owned(),
-                    found_in: "let_builtin_type".to_owned()
+                    expr: Expression::Literal {
+                        line: 1,
+                        column: 15,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 13, 15),
+                            ttype: TokenType::Integer,
+                            lexeme: "10".into(),
+                            suffix: None,
+                        }
+                    }
                 })
-            }))
-        )),
+            )
+        },
         found.ok().unwrap()
     );
 }
@@ -57,33 +62,40 @@ fn let_custom_type() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::DontCare,
-                lexeme: "_".to_owned(),
-                found_in: "let_custom_type".to_owned()
-            }),
-            Some(DataType::Compound {
-                name: Box::new(Token {
-                    line: 1,
-                    column: 8,
-                    ttype: TokenType::Identifier,
-                    lexeme: "Hello".to_owned(),
-                    found_in: "let_custom_type".to_owned()
-                })
-            }),
-            Box::new(ScopeBoundStatement::Expression(Expression::Literal {
-                literal: Box::new(Token {
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 5),
+                    ttype: TokenType::DontCare,
+                    lexeme: "_".into(),
+                    suffix: None,
+                },
+                Some(DataType::Compound {
+                    name: Box::new(Token {
+                        span: Span::new(FileId::default(), 8, 13),
+                        ttype: TokenType::Identifier,
+                        lexeme: "Hello".into(),
+                        suffix: None,
+                    })
+                }),
+                Box::new(ScopeBoundStatement::Expression {
                     line: 1,
                     column: 16,
-                    ttype: TokenType::Integer,
-                    lexeme: "10".to_owned(),
-                    found_in: "let_custom_type".to_owned()
+                    expr: Expression::Literal {
+                        line: 1,
+                        column: 18,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 16, 18),
+                            ttype: TokenType::Integer,
+                            lexeme: "10".into(),
+                            suffix: None,
+                        }
+                    }
                 })
-            }))
-        )),
+            )
+        },
         found.ok().unwrap()
     );
 }
@@ -96,11 +108,10 @@ fn let_invalid_type() {
     assert_eq!(
         ParseError::InvalidDataType {
             token: Box::new(Token {
-                line: 1,
-                column: 8,
+                span: Span::new(FileId::default(), 8, 9),
                 ttype: TokenType::Integer,
-                lexeme: "8".to_string(),
-                found_in: "let_invalid_type".to_string()
+                lexeme: "8".into(),
+                suffix: None,
             }),
             msg: None
         },
@@ -116,11 +127,10 @@ fn let_while_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 21),
                 ttype: TokenType::While,
-                lexeme: "while".to_owned(),
-                found_in: "let_while_value".to_owned()
+                lexeme: "while".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -135,11 +145,10 @@ fn let_loop_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 20),
                 ttype: TokenType::Loop,
-                lexeme: "loop".to_owned(),
-                found_in: "let_loop_value".to_owned()
+                lexeme: "loop".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -157,11 +166,10 @@ fn let_for_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 19),
                 ttype: TokenType::For,
-                lexeme: "for".to_owned(),
-                found_in: "let_for_value".to_owned()
+                lexeme: "for".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -176,11 +184,10 @@ fn let_return_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 22),
                 ttype: TokenType::Return,
-                lexeme: "return".to_owned(),
-                found_in: "let_return_value".to_owned()
+                lexeme: "return".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -195,11 +202,10 @@ fn let_let_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 19),
                 ttype: TokenType::Let,
-                lexeme: "let".to_owned(),
-                found_in: "let_let_value".to_owned()
+                lexeme: "let".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -214,11 +220,10 @@ fn let_break_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 21),
                 ttype: TokenType::Break,
-                lexeme: "break".to_owned(),
-                found_in: "let_break_value".to_owned()
+                lexeme: "break".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -233,11 +238,10 @@ fn let_continue_value() {
     assert_eq!(
         ParseError::InvalidVariableAssignment {
             value: Box::new(Token {
-                line: 1,
-                column: 16,
+                span: Span::new(FileId::default(), 16, 24),
                 ttype: TokenType::Continue,
-                lexeme: "continue".to_owned(),
-                found_in: "let_continue_value".to_owned()
+                lexeme: "continue".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -250,29 +254,35 @@ fn let_if_value() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::Identifier,
-                lexeme: "name".to_owned(),
-                found_in: "let_if_value".to_owned()
-            }),
-            Some(DataType::Isize),
-            Box::new(ScopeBoundStatement::Conditional {
-                condition: Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 22,
-                        ttype: TokenType::True,
-                        lexeme: "true".to_owned(),
-                        found_in: "let_if_value".to_owned()
-                    })
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 8),
+                    ttype: TokenType::Identifier,
+                    lexeme: "name".into(),
+                    suffix: None,
                 },
-                true_branch: vec![],
-                false_branch: None
-            })
-        )),
+                Some(DataType::Isize),
+                Box::new(ScopeBoundStatement::Conditional {
+                    line: 1,
+                    column: 19,
+                    condition: Expression::Literal {
+                        line: 1,
+                        column: 27,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 22, 26),
+                            ttype: TokenType::True,
+                            lexeme: "true".into(),
+                            suffix: None,
+                        }
+                    },
+                    true_branch: vec![],
+                    false_branch: None
+                })
+            )
+        },
         found.ok().unwrap()
     );
 }
@@ -283,28 +293,34 @@ fn let_match_value() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::Identifier,
-                lexeme: "name".to_owned(),
-                found_in: "let_match_value".to_owned()
-            }),
-            Some(DataType::Isize),
-            Box::new(ScopeBoundStatement::Match {
-                on: Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 25,
-                        ttype: TokenType::Integer,
-                        lexeme: "42".to_owned(),
-                        found_in: "let_match_value".to_owned()
-                    })
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 8),
+                    ttype: TokenType::Identifier,
+                    lexeme: "name".into(),
+                    suffix: None,
                 },
-                cases: HashMap::new()
-            })
-        )),
+                Some(DataType::Isize),
+                Box::new(ScopeBoundStatement::Match {
+                    line: 1,
+                    column: 19,
+                    on: Expression::Literal {
+                        line: 1,
+                        column: 28,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 25, 27),
+                            ttype: TokenType::Integer,
+                            lexeme: "42".into(),
+                            suffix: None,
+                        }
+                    },
+                    cases: vec![]
+                })
+            )
+        },
         found.ok().unwrap()
     );
 }
@@ -315,17 +331,24 @@ fn let_scope_value() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::Identifier,
-                lexeme: "name".to_owned(),
-                found_in: "let_scope_value".to_owned()
-            }),
-            Some(DataType::Isize),
-            Box::new(ScopeBoundStatement::Scope(vec![]))
-        )),
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 8),
+                    ttype: TokenType::Identifier,
+                    lexeme: "name".into(),
+                    suffix: None,
+                },
+                Some(DataType::Isize),
+                Box::new(ScopeBoundStatement::Scope {
+                    line: 1,
+                    column: 19,
+                    body: vec![]
+                })
+            )
+        },
         found.ok().unwrap()
     );
 }
@@ -336,43 +359,53 @@ fn let_expr_value() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::VariableDeclaration(Variable::new(
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::Identifier,
-                lexeme: "name".to_owned(),
-                found_in: "let_expr_value".to_owned()
-            }),
-            Some(DataType::Isize),
-            Box::new(ScopeBoundStatement::Expression(Expression::Binary {
-                left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 19,
-                        ttype: TokenType::Integer,
-                        lexeme: "12".to_owned(),
-                        found_in: "let_expr_value".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
+        ScopeBoundStatement::VariableDeclaration {
+            line: 1,
+            column: 0,
+            var: Variable::new(
+                Token {
+                    span: Span::new(FileId::default(), 4, 8),
+                    ttype: TokenType::Identifier,
+                    lexeme: "name".into(),
+                    suffix: None,
+                },
+                Some(DataType::Isize),
+                Box::new(ScopeBoundStatement::Expression {
                     line: 1,
-                    column: 22,
-                    ttype: TokenType::Plus,
-                    lexeme: "+".to_owned(),
-                    found_in: "let_expr_value".to_owned()
-                }),
-                right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
+                    column: 19,
+                    expr: Expression::Binary {
                         line: 1,
                         column: 24,
-                        ttype: TokenType::Integer,
-                        lexeme: "30".to_owned(),
-                        found_in: "let_expr_value".to_owned()
-                    })
+                        left: Box::new(Expression::Literal {
+                            line: 1,
+                            column: 22,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 19, 21),
+                                ttype: TokenType::Integer,
+                                lexeme: "12".into(),
+                                suffix: None,
+                            }
+                        }),
+                        operation: Token {
+                            span: Span::new(FileId::default(), 22, 23),
+                            ttype: TokenType::Plus,
+                            lexeme: "+".into(),
+                            suffix: None,
+                        },
+                        right: Box::new(Expression::Literal {
+                            line: 1,
+                            column: 26,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 24, 26),
+                                ttype: TokenType::Integer,
+                                lexeme: "30".into(),
+                                suffix: None,
+                            }
+                        })
+                    }
                 })
-            }))
-        )),
+            )
+        },
         found.ok().unwrap()
     );
 }
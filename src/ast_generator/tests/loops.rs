@@ -9,11 +9,10 @@ fn while_no_condition() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 6,
+                span: Span::new(FileId::default(), 6, 7),
                 ttype: TokenType::LeftBrace,
-                lexeme: "{".to_owned(),
-                found_in: "while_no_condition".to_owned()
+                lexeme: "{".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -28,11 +27,10 @@ fn while_no_condition_body() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 5,
+                span: Span::new(FileId::default(), 5, 6),
                 ttype: TokenType::Semicolon,
-                lexeme: ";".to_owned(),
-                found_in: "while_no_condition_body".to_owned()
+                lexeme: ";".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -46,14 +44,17 @@ fn valid_while_no_body() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::While {
+            line: 1,
+            column: 0,
             condition: Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 6,
+                line: 1,
+                column: 10,
+                literal: Token {
+                    span: Span::new(FileId::default(), 6, 10),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "valid_while_no_body".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             },
             body: None
         },
@@ -68,42 +69,52 @@ fn valid_while() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::While {
+            line: 1,
+            column: 0,
             condition: Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 6,
+                line: 1,
+                column: 10,
+                literal: Token {
+                    span: Span::new(FileId::default(), 6, 9),
                     ttype: TokenType::Nil,
-                    lexeme: "nil".to_owned(),
-                    found_in: "valid_while".to_owned()
-                })
+                    lexeme: "nil".into(),
+                    suffix: None,
+                }
             },
-            body: Some(vec![ScopeBoundStatement::Expression(Expression::Binary {
-                left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 12,
-                        ttype: TokenType::Integer,
-                        lexeme: "23".to_owned(),
-                        found_in: "valid_while".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
+            body: Some(vec![ScopeBoundStatement::Expression {
+                line: 1,
+                column: 12,
+                expr: Expression::Binary {
                     line: 1,
-                    column: 15,
-                    ttype: TokenType::Plus,
-                    lexeme: "+".to_owned(),
-                    found_in: "valid_while".to_owned()
-                }),
-                right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
+                    column: 17,
+                    left: Box::new(Expression::Literal {
                         line: 1,
-                        column: 17,
-                        ttype: TokenType::Integer,
-                        lexeme: "19".to_owned(),
-                        found_in: "valid_while".to_owned()
+                        column: 15,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 12, 14),
+                            ttype: TokenType::Integer,
+                            lexeme: "23".into(),
+                            suffix: None,
+                        }
+                    }),
+                    operation: Token {
+                        span: Span::new(FileId::default(), 15, 16),
+                        ttype: TokenType::Plus,
+                        lexeme: "+".into(),
+                        suffix: None,
+                    },
+                    right: Box::new(Expression::Literal {
+                        line: 1,
+                        column: 19,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 17, 19),
+                            ttype: TokenType::Integer,
+                            lexeme: "19".into(),
+                            suffix: None,
+                        }
                     })
-                })
-            })])
+                }
+            }])
         },
         found.ok().unwrap()
     );
@@ -116,6 +127,8 @@ fn empty_for_no_body() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
+            line: 1,
+            column: 0,
             initialization: None,
             condition: None,
             increment: None,
@@ -132,6 +145,8 @@ fn empty_for() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
+            line: 1,
+            column: 0,
             initialization: None,
             condition: None,
             increment: None,
@@ -148,27 +163,35 @@ fn for_with_init() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
-            initialization: Some(Box::new(ScopeBoundStatement::VariableDeclaration(
-                Variable::new(
-                    Box::new(Token {
-                        line: 1,
-                        column: 9,
+            line: 1,
+            column: 0,
+            initialization: Some(Box::new(ScopeBoundStatement::VariableDeclaration {
+                line: 1,
+                column: 5,
+                var: Variable::new(
+                    Token {
+                        span: Span::new(FileId::default(), 9, 10),
                         ttype: TokenType::Identifier,
-                        lexeme: "a".to_owned(),
-                        found_in: "for_with_init".to_owned()
-                    }),
+                        lexeme: "a".into(),
+                        suffix: None,
+                    },
                     None,
-                    Box::new(ScopeBoundStatement::Expression(Expression::Literal {
-                        literal: Box::new(Token {
+                    Box::new(ScopeBoundStatement::Expression {
+                        line: 1,
+                        column: 14,
+                        expr: Expression::Literal {
                             line: 1,
-                            column: 14,
-                            ttype: TokenType::Integer,
-                            lexeme: "0".to_owned(),
-                            found_in: "for_with_init".to_owned()
-                        })
-                    }))
+                            column: 15,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 14, 15),
+                                ttype: TokenType::Integer,
+                                lexeme: "0".into(),
+                                suffix: None,
+                            }
+                        }
+                    })
                 )
-            ))),
+            })),
             condition: None,
             increment: None,
             body: None
@@ -191,15 +214,18 @@ fn for_with_condition() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
+            line: 1,
+            column: 0,
             initialization: None,
             condition: Some(Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 7,
+                line: 1,
+                column: 11,
+                literal: Token {
+                    span: Span::new(FileId::default(), 7, 11),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "for_with_condition".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             }),
             increment: None,
             body: None
@@ -219,11 +245,10 @@ fn for_with_invalid_condition() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 7,
+                span: Span::new(FileId::default(), 7, 9),
                 ttype: TokenType::If,
-                lexeme: "if".to_owned(),
-                found_in: "for_with_invalid_condition".to_owned()
+                lexeme: "if".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -237,33 +262,38 @@ fn for_with_increment() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
+            line: 1,
+            column: 0,
             initialization: None,
             condition: None,
             increment: Some(Expression::Binary {
+                line: 1,
+                column: 16,
                 left: Box::new(Expression::Name {
-                    name: Box::new(Token {
-                        line: 1,
-                        column: 9,
-                        ttype: TokenType::Identifier,
-                        lexeme: "a".to_owned(),
-                        found_in: "for_with_increment".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
                     line: 1,
                     column: 11,
-                    ttype: TokenType::PlusEquals,
-                    lexeme: "+=".to_owned(),
-                    found_in: "for_with_increment".to_owned()
+                    name: Token {
+                        span: Span::new(FileId::default(), 9, 10),
+                        ttype: TokenType::Identifier,
+                        lexeme: "a".into(),
+                        suffix: None,
+                    }
                 }),
+                operation: Token {
+                    span: Span::new(FileId::default(), 11, 13),
+                    ttype: TokenType::PlusEquals,
+                    lexeme: "+=".into(),
+                    suffix: None,
+                },
                 right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 14,
+                    line: 1,
+                    column: 16,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 14, 16),
                         ttype: TokenType::Integer,
-                        lexeme: "10".to_owned(),
-                        found_in: "for_with_increment".to_owned()
-                    })
+                        lexeme: "10".into(),
+                        suffix: None,
+                    }
                 })
             }),
             body: None
@@ -283,11 +313,10 @@ fn for_with_invalid_increment() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 9,
+                span: Span::new(FileId::default(), 9, 11),
                 ttype: TokenType::If,
-                lexeme: "if".to_owned(),
-                found_in: "for_with_invalid_increment".to_owned()
+                lexeme: "if".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -301,61 +330,73 @@ fn full_for() {
     assert!(found.is_ok());
     assert_eq!(
         ScopeBoundStatement::For {
-            initialization: Some(Box::new(ScopeBoundStatement::VariableDeclaration(
-                Variable::new(
-                    Box::new(Token {
-                        line: 1,
-                        column: 9,
+            line: 1,
+            column: 0,
+            initialization: Some(Box::new(ScopeBoundStatement::VariableDeclaration {
+                line: 1,
+                column: 5,
+                var: Variable::new(
+                    Token {
+                        span: Span::new(FileId::default(), 9, 10),
                         ttype: TokenType::Identifier,
-                        lexeme: "a".to_owned(),
-                        found_in: "full_for".to_owned()
-                    }),
+                        lexeme: "a".into(),
+                        suffix: None,
+                    },
                     None,
-                    Box::new(ScopeBoundStatement::Expression(Expression::Literal {
-                        literal: Box::new(Token {
+                    Box::new(ScopeBoundStatement::Expression {
+                        line: 1,
+                        column: 13,
+                        expr: Expression::Literal {
                             line: 1,
-                            column: 13,
-                            ttype: TokenType::Integer,
-                            lexeme: "0".to_owned(),
-                            found_in: "full_for".to_owned()
-                        })
-                    }))
+                            column: 14,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 13, 14),
+                                ttype: TokenType::Integer,
+                                lexeme: "0".into(),
+                                suffix: None,
+                            }
+                        }
+                    })
                 )
-            ))),
+            })),
             condition: Some(Expression::Literal {
-                literal: Box::new(Token {
-                    line: 1,
-                    column: 16,
+                line: 1,
+                column: 20,
+                literal: Token {
+                    span: Span::new(FileId::default(), 16, 20),
                     ttype: TokenType::True,
-                    lexeme: "true".to_owned(),
-                    found_in: "full_for".to_owned()
-                })
+                    lexeme: "true".into(),
+                    suffix: None,
+                }
             }),
             increment: Some(Expression::Binary {
+                line: 1,
+                column: 29,
                 left: Box::new(Expression::Name {
-                    name: Box::new(Token {
-                        line: 1,
-                        column: 22,
-                        ttype: TokenType::Identifier,
-                        lexeme: "a".to_owned(),
-                        found_in: "full_for".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
                     line: 1,
                     column: 24,
-                    ttype: TokenType::PlusEquals,
-                    lexeme: "+=".to_owned(),
-                    found_in: "full_for".to_owned()
+                    name: Token {
+                        span: Span::new(FileId::default(), 22, 23),
+                        ttype: TokenType::Identifier,
+                        lexeme: "a".into(),
+                        suffix: None,
+                    }
                 }),
+                operation: Token {
+                    span: Span::new(FileId::default(), 24, 26),
+                    ttype: TokenType::PlusEquals,
+                    lexeme: "+=".into(),
+                    suffix: None,
+                },
                 right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 27,
+                    line: 1,
+                    column: 29,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 27, 29),
                         ttype: TokenType::Integer,
-                        lexeme: "10".to_owned(),
-                        found_in: "full_for".to_owned()
-                    })
+                        lexeme: "10".into(),
+                        suffix: None,
+                    }
                 })
             }),
             body: None
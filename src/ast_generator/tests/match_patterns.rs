@@ -0,0 +1,230 @@
+use super::*;
+use crate::ast_generator::ast::scopebound_statements::MatchCase;
+
+fn cases_of(stmt: ScopeBoundStatement) -> Vec<MatchCase> {
+    match stmt {
+        ScopeBoundStatement::Match { cases, .. } => {
+            cases.into_iter().map(|(case, _)| case).collect()
+        }
+        stmt => panic!("expected a match, found {stmt:?}"),
+    }
+}
+
+fn lexemes_of(patterns: &[Expression]) -> Vec<&str> {
+    patterns
+        .iter()
+        .map(|pattern| match pattern {
+            Expression::Literal { literal, .. } => literal.lexeme.as_str(),
+            Expression::Name { name, .. } => name.lexeme.as_str(),
+            pattern => panic!("expected a literal or a name, found {pattern:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn or_pattern() {
+    let found = parse("or_pattern", "match n { 1 | 2 | 3 -> { a; } }");
+    let cases = cases_of(found.ok().unwrap());
+
+    match &cases[..] {
+        [MatchCase {
+            pattern: Expression::Alternatives { patterns, .. },
+            guard: None,
+        }] => assert_eq!(vec!["1", "2", "3"], lexemes_of(patterns)),
+        cases => panic!("expected a single or-pattern, found {cases:?}"),
+    }
+}
+
+#[test]
+fn nested_or_pattern() {
+    let found = parse(
+        "nested_or_pattern",
+        "match s { Shape::Circle(1 | 2) -> { a; } }",
+    );
+    let cases = cases_of(found.ok().unwrap());
+
+    match &cases[..] {
+        [MatchCase {
+            pattern:
+                Expression::EnumVariant {
+                    payload: Some(payload),
+                    ..
+                },
+            ..
+        }] => assert!(
+            matches!(**payload, Expression::Alternatives { ref patterns, .. } if patterns.len() == 2)
+        ),
+        cases => panic!("expected a single enum variant pattern, found {cases:?}"),
+    }
+}
+
+#[test]
+fn guarded_case() {
+    let found = parse(
+        "guarded_case",
+        "match n { x if x > 10 -> { a; }, x if x < 0 -> { b; }, _ -> { c; } }",
+    );
+    let cases = cases_of(found.ok().unwrap());
+
+    // same pattern, different guards: both arms are kept
+    assert_eq!(3, cases.len());
+    let guards: Vec<&Expression> = cases
+        .iter()
+        .filter_map(|case| case.guard.as_ref())
+        .collect();
+    assert_eq!(2, guards.len());
+    assert!(guards
+        .iter()
+        .all(|guard| matches!(guard, Expression::Binary { .. })));
+}
+
+#[test]
+fn guarded_or_pattern() {
+    let found = parse("guarded_or_pattern", "match n { 1 | 2 if ok -> { a; } }");
+    let cases = cases_of(found.ok().unwrap());
+
+    match &cases[..] {
+        [MatchCase {
+            pattern: Expression::Alternatives { patterns, .. },
+            guard: Some(Expression::Name { name, .. }),
+        }] => {
+            assert_eq!(vec!["1", "2"], lexemes_of(patterns));
+            assert_eq!("ok", name.lexeme);
+        }
+        cases => panic!("expected a single guarded or-pattern, found {cases:?}"),
+    }
+}
+
+#[test]
+fn malformed_patterns() {
+    for content in [
+        "match n { 1 | -> { a; } }",
+        "match n { x if -> { a; } }",
+        "match n { | 1 -> { a; } }",
+    ] {
+        assert!(
+            parse("malformed_patterns", content).is_err(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
+
+#[test]
+fn cases_keep_source_order() {
+    let found = parse(
+        "cases_keep_source_order",
+        "match n { 3 -> { a; }, 1 -> { b; }, 2 -> { c; }, _ -> { d; } }",
+    );
+    let patterns: Vec<Expression> = cases_of(found.ok().unwrap())
+        .into_iter()
+        .map(|case| case.pattern)
+        .collect();
+
+    assert_eq!(vec!["3", "1", "2", "_"], lexemes_of(&patterns));
+}
+
+#[test]
+fn duplicate_case() {
+    let (found, diagnostics) = parse_with_diagnostics(
+        "duplicate_case",
+        "match n { 1 -> { a; }, 2 -> { b; }, 1 -> { c; } }",
+    );
+
+    // the match is still parsed in full, arm included
+    assert_eq!(3, cases_of(found.ok().unwrap()).len());
+    match &diagnostics[..] {
+        [ParseError::DuplicateMatchArm { arm, first }] => {
+            assert_eq!((36, 10), (arm.span.start, first.span.start));
+        }
+        diagnostics => panic!("expected a duplicate arm, found {diagnostics:?}"),
+    }
+}
+
+#[test]
+fn unreachable_cases() {
+    for (content, arm, covered_by) in [
+        ("match n { _ -> { a; }, 1 -> { b; } }", 23, 10),
+        ("match n { x -> { a; }, 1 | 2 -> { b; } }", 23, 10),
+        ("match n { 0..9 -> { a; }, 4 -> { b; } }", 26, 10),
+        ("match n { 1 | 2 -> { a; }, 2 -> { b; } }", 27, 10),
+        (
+            "match s { Shape::Circle(_) -> { a; }, Shape::Circle(1) -> { b; } }",
+            38,
+            10,
+        ),
+    ] {
+        let (found, diagnostics) = parse_with_diagnostics("unreachable_cases", content);
+
+        assert!(found.is_ok(), "`{content}` should parse");
+        match &diagnostics[..] {
+            [ParseError::UnreachableMatchArm {
+                arm: found,
+                covered_by: found_covered_by,
+            }] => assert_eq!(
+                (arm, covered_by),
+                (found.span.start, found_covered_by.span.start),
+                "{content}"
+            ),
+            diagnostics => {
+                panic!("expected an unreachable arm in `{content}`, found {diagnostics:?}")
+            }
+        }
+    }
+}
+
+#[test]
+fn every_redundant_case_is_reported() {
+    let (found, diagnostics) = parse_with_diagnostics(
+        "every_redundant_case_is_reported",
+        "match n { 1 -> { a; }, 1 -> { b; }, x -> { c; }, 2 -> { d; } }",
+    );
+
+    assert_eq!(4, cases_of(found.ok().unwrap()).len());
+    match &diagnostics[..] {
+        [ParseError::DuplicateMatchArm { arm, first }, ParseError::UnreachableMatchArm {
+            arm: unreachable,
+            covered_by,
+        }] => {
+            assert_eq!((23, 10), (arm.span.start, first.span.start));
+            assert_eq!((49, 36), (unreachable.span.start, covered_by.span.start));
+        }
+        diagnostics => panic!("expected a duplicate and an unreachable arm, found {diagnostics:?}"),
+    }
+}
+
+#[test]
+fn guarded_cases_are_reachable_past() {
+    for content in [
+        "match n { _ if ok -> { a; }, 1 -> { b; } }",
+        "match n { 1 if ok -> { a; }, 1 -> { b; }, 2 -> { c; } }",
+        "match s { Shape::Circle(1) -> { a; }, Shape::Square(1) -> { b; } }",
+    ] {
+        let (found, diagnostics) =
+            parse_with_diagnostics("guarded_cases_are_reachable_past", content);
+        assert!(
+            found.is_ok() && diagnostics.is_empty(),
+            "`{content}` should parse without diagnostics"
+        );
+    }
+}
+
+#[test]
+fn range_bounds_up_to_u64() {
+    let (found, diagnostics) = parse_with_diagnostics(
+        "range_bounds_up_to_u64",
+        "match n { 0..0xFFFF_FFFF_FFFF_FFFF -> { a; }, 0xFFFF_FFFF_FFFF_FFFE -> { b; } }",
+    );
+
+    match &cases_of(found.ok().unwrap())[0].pattern {
+        Expression::Sequence { start, end, .. } => {
+            assert_eq!((0, u64::MAX as i128 - 1), (*start, *end))
+        }
+        pattern => panic!("expected a range, found {pattern:?}"),
+    }
+    match &diagnostics[..] {
+        [ParseError::UnreachableMatchArm { arm, covered_by }] => {
+            assert_eq!((46, 10), (arm.span.start, covered_by.span.start));
+        }
+        diagnostics => panic!("expected an unreachable arm, found {diagnostics:?}"),
+    }
+}
@@ -1,26 +1,60 @@
-use super::ast::{expressions::Expression, scopebound_statements::ScopeBoundStatement};
+use std::sync::{Arc, Mutex};
+
+use super::ast::{expressions::Expression, scopebound_statements::ScopeBoundStatement, ASTs};
+use super::parser;
 use super::parser_head::ParserHead;
 use super::statement_parser::parse_scopebound_statement;
 
-use crate::{
-    test_util::{create_test_file, delete_test_file},
-    tokens::{
-        error::ParseError, source::SourceFile, token::Token, token_type::TokenType, tokenizer,
-    },
+use crate::tokens::{
+    error::ParseError,
+    source::SourceFile,
+    source_map::{FileId, SourceMap, Span},
+    token::Token,
+    token_type::TokenType,
+    tokenizer,
 };
 
 fn parse(file_name: &str, content: &str) -> Result<ScopeBoundStatement, ParseError> {
-    create_test_file(file_name, content);
-    let mut file = SourceFile::new(file_name).unwrap();
+    parse_with_diagnostics(file_name, content).0
+}
+
+/// Parses a whole file: its imports, functions, structs and enums.
+fn parse_items(file_name: &str, content: &str) -> ASTs {
+    let mut file = SourceFile::from_string(file_name, content.to_owned());
+    parser::parse_source(&mut file, Arc::new(Mutex::new(SourceMap::new())))
+}
 
-    let mut head = ParserHead::new(tokenizer::get_token(&mut file), Box::new(Token::new()), &mut file);
+/// Like `parse`, also giving back the diagnostics that didn't stop the parse.
+fn parse_with_diagnostics(
+    file_name: &str,
+    content: &str,
+) -> (Result<ScopeBoundStatement, ParseError>, Vec<ParseError>) {
+    let mut file = SourceFile::from_string(file_name, content.to_owned());
+    let mut head = ParserHead::new(tokenizer::get_token(&mut file), Token::new(), &mut file);
 
-    delete_test_file(file_name);
-    parse_scopebound_statement(&mut head)
+    let res = parse_scopebound_statement(&mut head);
+    (res, head.diagnostics)
 }
 
+/// The expression of an expression statement.
+fn expression_of(stmt: ScopeBoundStatement) -> Expression {
+    match stmt {
+        ScopeBoundStatement::Expression { expr, .. } => expr,
+        stmt => panic!("expected an expression, found {stmt:?}"),
+    }
+}
+
+mod cast;
 mod conditional;
+mod doc_comments;
+mod enum_definition;
+mod enum_variant;
+mod index;
+mod interpolation;
+mod let_stmt;
 mod loops;
+mod match_patterns;
+mod operators;
 mod scope;
 mod simple_stmt;
-mod let_stmt;
+mod struct_literal;
@@ -0,0 +1,191 @@
+use super::*;
+
+fn operator_of(stmt: ScopeBoundStatement) -> TokenType {
+    match stmt {
+        ScopeBoundStatement::Expression {
+            expr: Expression::Binary { operation, .. } | Expression::Unary { operation, .. },
+            ..
+        } => operation.ttype,
+        stmt => panic!("expected an operation, found {stmt:?}"),
+    }
+}
+
+#[test]
+fn every_compound_assignment() {
+    let expected = vec![
+        ("x += 1;", TokenType::PlusEquals),
+        ("x -= 1;", TokenType::MinusEquals),
+        ("x *= 1;", TokenType::StarEquals),
+        ("x /= 1;", TokenType::SlashEquals),
+        ("x //= 1;", TokenType::IntegerSlashEquals),
+        ("x %= 1;", TokenType::ModEquals),
+        ("x **= 1;", TokenType::PowerEquals),
+        ("x &= 1;", TokenType::BitAndEquals),
+        ("x |= 1;", TokenType::BitOrEquals),
+        ("x ^= 1;", TokenType::BitXorEquals),
+        ("x <<= 1;", TokenType::ShiftLeftEqual),
+        ("x >>= 1;", TokenType::ShiftRightEqual),
+    ];
+
+    for (content, ttype) in expected {
+        let found = parse("every_compound_assignment", content);
+
+        assert!(found.is_ok(), "`{content}` should parse");
+        assert_eq!(ttype, operator_of(found.ok().unwrap()));
+    }
+}
+
+#[test]
+fn xor_and_power() {
+    let found = parse("xor", "a ^ b;");
+    assert_eq!(TokenType::BitXor, operator_of(found.ok().unwrap()));
+
+    let found = parse("power", "a ** b;");
+    assert_eq!(TokenType::Power, operator_of(found.ok().unwrap()));
+}
+
+#[test]
+fn bitwise_not() {
+    let found = parse("bitwise_not", "~~a;");
+
+    match found.ok().unwrap() {
+        ScopeBoundStatement::Expression {
+            expr: Expression::Unary {
+                operation, value, ..
+            },
+            ..
+        } => {
+            assert_eq!(TokenType::BitNot, operation.ttype);
+            assert!(matches!(
+                *value,
+                Expression::Unary { ref operation, .. } if operation.ttype == TokenType::BitNot
+            ));
+        }
+        stmt => panic!("expected a unary expression, found {stmt:?}"),
+    }
+}
+
+#[test]
+fn compound_assignment_to_literal() {
+    let found = parse("compound_assignment_to_literal", "1 ^= 2;");
+
+    assert!(matches!(
+        found.err().unwrap(),
+        ParseError::InvalidAssignmentExpression { operation, .. }
+            if operation.ttype == TokenType::BitXorEquals
+    ));
+}
+
+#[test]
+fn bitwise_binds_tighter_than_comparisons() {
+    let expected = vec![
+        ("a & 1 == 0;", TokenType::BitAnd),
+        ("a | b != c;", TokenType::BitOr),
+        ("a ^ b < c;", TokenType::BitXor),
+    ];
+
+    for (content, ttype) in expected {
+        match expression_of(parse("bitwise_binds_tighter_than_comparisons", content).unwrap()) {
+            Expression::Binary { left, .. } => assert!(
+                matches!(*left, Expression::Binary { ref operation, .. } if operation.ttype == ttype),
+                "`{content}` should group its bitwise operation first"
+            ),
+            expr => panic!("expected a binary expression, found {expr:?}"),
+        }
+    }
+
+    // `&` binds tighter than `|` and `^`, both looser than `+`
+    match expression_of(parse("bitwise_and_before_or", "a | b & c + 1;").unwrap()) {
+        Expression::Binary {
+            operation, right, ..
+        } => {
+            assert_eq!(TokenType::BitOr, operation.ttype);
+            assert!(matches!(
+                *right,
+                Expression::Binary { ref operation, .. } if operation.ttype == TokenType::BitAnd
+            ));
+        }
+        expr => panic!("expected a binary expression, found {expr:?}"),
+    }
+}
+
+/// The operator of a binary expression with the ones of its operands, `None`
+/// for an operand which isn't an operation.
+fn grouping_of(content: &str) -> (Option<TokenType>, TokenType, Option<TokenType>) {
+    let operator = |expr: &Expression| match expr {
+        Expression::Binary { operation, .. } | Expression::Unary { operation, .. } => {
+            Some(operation.ttype.clone())
+        }
+        _ => None,
+    };
+
+    match expression_of(parse("grouping", content).unwrap()) {
+        Expression::Binary {
+            left,
+            operation,
+            right,
+            ..
+        } => (operator(&left), operation.ttype, operator(&right)),
+        expr => panic!("expected `{content}` to be a binary expression, found {expr:?}"),
+    }
+}
+
+#[test]
+fn remainder_binds_like_multiplication() {
+    assert_eq!(
+        (None, TokenType::Plus, Some(TokenType::Mod)),
+        grouping_of("a + b % c;")
+    );
+    assert_eq!(
+        (Some(TokenType::Mod), TokenType::Star, None),
+        grouping_of("a % b * c;")
+    );
+}
+
+#[test]
+fn shifts_bind_between_addition_and_bitwise_and() {
+    assert_eq!(
+        (None, TokenType::ShiftLeft, Some(TokenType::Plus)),
+        grouping_of("1 << 2 + 3;")
+    );
+    assert_eq!(
+        (Some(TokenType::Minus), TokenType::ShiftRight, None),
+        grouping_of("a - 1 >> b;")
+    );
+    assert_eq!(
+        (Some(TokenType::ShiftLeft), TokenType::BitAnd, None),
+        grouping_of("a << 1 & b;")
+    );
+}
+
+#[test]
+fn power_is_right_associative() {
+    assert_eq!(
+        (None, TokenType::Power, Some(TokenType::Power)),
+        grouping_of("2 ** 3 ** 2;")
+    );
+    assert_eq!(
+        (Some(TokenType::Power), TokenType::Star, None),
+        grouping_of("2 ** 3 * 2;")
+    );
+    assert_eq!(
+        (None, TokenType::Power, Some(TokenType::Minus)),
+        grouping_of("2 ** -1;")
+    );
+}
+
+#[test]
+fn power_binds_tighter_than_negation() {
+    match expression_of(parse("power_binds_tighter_than_negation", "-2 ** 2;").unwrap()) {
+        Expression::Unary {
+            operation, value, ..
+        } => {
+            assert_eq!(TokenType::Minus, operation.ttype);
+            assert!(matches!(
+                *value,
+                Expression::Binary { ref operation, .. } if operation.ttype == TokenType::Power
+            ));
+        }
+        expr => panic!("expected a negated power, found {expr:?}"),
+    }
+}
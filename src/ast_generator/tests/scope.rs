@@ -9,8 +9,7 @@ fn scope_not_closed() {
         ParseError::UnexpectedToken {
             token, expected, ..
         } => {
-            assert_eq!(1, token.line);
-            assert_eq!(8, token.column);
+            assert_eq!(8, token.span.start);
             assert_eq!(TokenType::Eof, token.ttype);
             assert_eq!(TokenType::RightBrace, expected);
         }
@@ -27,8 +26,7 @@ fn invalid_stmt_in_invalid_subscope() {
         ParseError::UnexpectedToken {
             token, expected, ..
         } => {
-            assert_eq!(1, token.line);
-            assert_eq!(10, token.column);
+            assert_eq!(10, token.span.start);
             assert_eq!(TokenType::Eof, token.ttype);
             assert_eq!(TokenType::RightBrace, expected);
         }
@@ -42,35 +40,48 @@ fn inner_scope_implicit_return() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::Scope(vec![ScopeBoundStatement::Scope(vec![
-            ScopeBoundStatement::ImplicitReturn(Expression::Binary {
-                left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 3,
-                        ttype: TokenType::Integer,
-                        lexeme: "3".to_owned(),
-                        found_in: "inner_scope_implicit_return".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
+        ScopeBoundStatement::Scope {
+            line: 1,
+            column: 0,
+            body: vec![ScopeBoundStatement::Scope {
+                line: 1,
+                column: 2,
+                body: vec![ScopeBoundStatement::ImplicitReturn {
                     line: 1,
-                    column: 5,
-                    ttype: TokenType::Plus,
-                    lexeme: "+".to_owned(),
-                    found_in: "inner_scope_implicit_return".to_owned()
-                }),
-                right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
+                    column: 3,
+                    expr: Expression::Binary {
                         line: 1,
                         column: 7,
-                        ttype: TokenType::Integer,
-                        lexeme: "4".to_owned(),
-                        found_in: "inner_scope_implicit_return".to_owned()
-                    })
-                })
-            })
-        ])]),
+                        left: Box::new(Expression::Literal {
+                            line: 1,
+                            column: 5,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 3, 4),
+                                ttype: TokenType::Integer,
+                                lexeme: "3".into(),
+                                suffix: None,
+                            }
+                        }),
+                        operation: Token {
+                            span: Span::new(FileId::default(), 5, 6),
+                            ttype: TokenType::Plus,
+                            lexeme: "+".into(),
+                            suffix: None,
+                        },
+                        right: Box::new(Expression::Literal {
+                            line: 1,
+                            column: 8,
+                            literal: Token {
+                                span: Span::new(FileId::default(), 7, 8),
+                                ttype: TokenType::Integer,
+                                lexeme: "4".into(),
+                                suffix: None,
+                            }
+                        })
+                    }
+                }]
+            }]
+        },
         found.ok().unwrap()
     );
 }
@@ -80,31 +91,39 @@ fn valid_scope() {
     let found = parse("valid_scope", "{ { 3 + 4; } }");
     assert!(found.is_ok());
 
-    if let ScopeBoundStatement::Scope(body) = found.ok().unwrap() {
+    if let ScopeBoundStatement::Scope { body, .. } = found.ok().unwrap() {
         assert!(!body.is_empty());
-        assert!(body.get(0).is_some());
 
-        if let ScopeBoundStatement::Scope(inner_body) = &body[0] {
+        if let ScopeBoundStatement::Scope {
+            body: inner_body, ..
+        } = &body[0]
+        {
             assert!(!inner_body.is_empty());
-            assert!(inner_body.get(0).is_some());
 
-            if let ScopeBoundStatement::Expression(expr) = &inner_body[0] {
-                if let Expression::Binary {
-                    left,
-                    operation,
-                    right,
-                } = expr
+            if let ScopeBoundStatement::Expression {
+                expr:
+                    Expression::Binary {
+                        left,
+                        operation,
+                        right,
+                        ..
+                    },
+                ..
+            } = &inner_body[0]
+            {
+                if let (
+                    Expression::Literal {
+                        literal: left_lit, ..
+                    },
+                    Expression::Literal {
+                        literal: right_lit, ..
+                    },
+                ) = (left.as_ref(), right.as_ref())
                 {
-                    if let (
-                        Expression::Literal { literal: left_lit },
-                        Expression::Literal { literal: right_lit },
-                    ) = (left.as_ref(), right.as_ref())
-                    {
-                        assert_eq!("3", left_lit.lexeme);
-                        assert_eq!(TokenType::Plus, operation.ttype);
-                        assert_eq!("4", right_lit.lexeme);
-                        return;
-                    }
+                    assert_eq!("3", left_lit.lexeme);
+                    assert_eq!(TokenType::Plus, operation.ttype);
+                    assert_eq!("4", right_lit.lexeme);
+                    return;
                 }
             }
         }
@@ -8,11 +8,10 @@ fn defer_without_stmt() {
     assert_eq!(
         ParseError::InvalidExpression {
             token: Box::new(Token {
-                line: 1,
-                column: 5,
+                span: Span::new(FileId::default(), 5, 6),
                 ttype: TokenType::Semicolon,
-                lexeme: ";".to_owned(),
-                found_in: "defer_without_stmt".to_owned()
+                lexeme: ";".into(),
+                suffix: None,
             })
         },
         found.err().unwrap()
@@ -25,7 +24,15 @@ fn defer_empty_scope_stmt() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::Defer(Box::new(ScopeBoundStatement::Scope(vec![]))),
+        ScopeBoundStatement::Defer {
+            line: 1,
+            column: 0,
+            stmt: Box::new(ScopeBoundStatement::Scope {
+                line: 1,
+                column: 6,
+                body: vec![]
+            })
+        },
         found.ok().unwrap()
     );
 }
@@ -36,35 +43,44 @@ fn defer_stmt() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::Defer(Box::new(ScopeBoundStatement::Expression(
-            Expression::Binary {
-                left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 6,
-                        ttype: TokenType::Integer,
-                        lexeme: "2".to_owned(),
-                        found_in: "defer_stmt".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
+        ScopeBoundStatement::Defer {
+            line: 1,
+            column: 0,
+            stmt: Box::new(ScopeBoundStatement::Expression {
+                line: 1,
+                column: 6,
+                expr: Expression::Binary {
                     line: 1,
-                    column: 8,
-                    ttype: TokenType::Plus,
-                    lexeme: "+".to_owned(),
-                    found_in: "defer_stmt".to_owned()
-                }),
-                right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
+                    column: 10,
+                    left: Box::new(Expression::Literal {
+                        line: 1,
+                        column: 8,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 6, 7),
+                            ttype: TokenType::Integer,
+                            lexeme: "2".into(),
+                            suffix: None,
+                        }
+                    }),
+                    operation: Token {
+                        span: Span::new(FileId::default(), 8, 9),
+                        ttype: TokenType::Plus,
+                        lexeme: "+".into(),
+                        suffix: None,
+                    },
+                    right: Box::new(Expression::Literal {
                         line: 1,
-                        column: 10,
-                        ttype: TokenType::Integer,
-                        lexeme: "40".to_owned(),
-                        found_in: "defer_stmt".to_owned()
+                        column: 12,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 10, 12),
+                            ttype: TokenType::Integer,
+                            lexeme: "40".into(),
+                            suffix: None,
+                        }
                     })
-                })
-            }
-        ))),
+                }
+            })
+        },
         found.ok().unwrap()
     );
 }
@@ -74,7 +90,14 @@ fn return_nothing() {
     let found = parse("return_nothing", "return;");
 
     assert!(found.is_ok());
-    assert_eq!(ScopeBoundStatement::Return(None), found.ok().unwrap());
+    assert_eq!(
+        ScopeBoundStatement::Return {
+            line: 1,
+            column: 0,
+            value: None
+        },
+        found.ok().unwrap()
+    );
 }
 
 #[test]
@@ -83,35 +106,44 @@ fn return_expr() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::Return(Some(Box::new(ScopeBoundStatement::Expression(
-            Expression::Binary {
-                left: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
-                        line: 1,
-                        column: 7,
-                        ttype: TokenType::Integer,
-                        lexeme: "2".to_owned(),
-                        found_in: "return_expr".to_owned()
-                    })
-                }),
-                operation: Box::new(Token {
+        ScopeBoundStatement::Return {
+            line: 1,
+            column: 0,
+            value: Some(Box::new(ScopeBoundStatement::Expression {
+                line: 1,
+                column: 7,
+                expr: Expression::Binary {
                     line: 1,
-                    column: 9,
-                    ttype: TokenType::Plus,
-                    lexeme: "+".to_owned(),
-                    found_in: "return_expr".to_owned()
-                }),
-                right: Box::new(Expression::Literal {
-                    literal: Box::new(Token {
+                    column: 11,
+                    left: Box::new(Expression::Literal {
                         line: 1,
-                        column: 11,
-                        ttype: TokenType::Integer,
-                        lexeme: "3".to_owned(),
-                        found_in: "return_expr".to_owned()
+                        column: 9,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 7, 8),
+                            ttype: TokenType::Integer,
+                            lexeme: "2".into(),
+                            suffix: None,
+                        }
+                    }),
+                    operation: Token {
+                        span: Span::new(FileId::default(), 9, 10),
+                        ttype: TokenType::Plus,
+                        lexeme: "+".into(),
+                        suffix: None,
+                    },
+                    right: Box::new(Expression::Literal {
+                        line: 1,
+                        column: 12,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 11, 12),
+                            ttype: TokenType::Integer,
+                            lexeme: "3".into(),
+                            suffix: None,
+                        }
                     })
-                })
-            }
-        )))),
+                }
+            }))
+        },
         found.ok().unwrap()
     );
 }
@@ -122,27 +154,39 @@ fn return_stmt() {
 
     assert!(found.is_ok());
     assert_eq!(
-        ScopeBoundStatement::Return(Some(Box::new(ScopeBoundStatement::Conditional {
-            condition: Expression::Literal {
-                literal: Box::new(Token {
+        ScopeBoundStatement::Return {
+            line: 1,
+            column: 0,
+            value: Some(Box::new(ScopeBoundStatement::Conditional {
+                line: 1,
+                column: 7,
+                condition: Expression::Literal {
                     line: 1,
-                    column: 10,
-                    ttype: TokenType::Nil,
-                    lexeme: "nil".to_owned(),
-                    found_in: "return_stmt".to_owned()
-                })
-            },
-            true_branch: vec![ScopeBoundStatement::ImplicitReturn(Expression::Literal {
-                literal: Box::new(Token {
+                    column: 14,
+                    literal: Token {
+                        span: Span::new(FileId::default(), 10, 13),
+                        ttype: TokenType::Nil,
+                        lexeme: "nil".into(),
+                        suffix: None,
+                    }
+                },
+                true_branch: vec![ScopeBoundStatement::ImplicitReturn {
                     line: 1,
                     column: 16,
-                    ttype: TokenType::Integer,
-                    lexeme: "23".to_owned(),
-                    found_in: "return_stmt".to_owned()
-                })
-            })],
-            false_branch: None
-        }))),
+                    expr: Expression::Literal {
+                        line: 1,
+                        column: 19,
+                        literal: Token {
+                            span: Span::new(FileId::default(), 16, 18),
+                            ttype: TokenType::Integer,
+                            lexeme: "23".into(),
+                            suffix: None,
+                        }
+                    }
+                }],
+                false_branch: None
+            }))
+        },
         found.ok().unwrap()
     );
 }
@@ -155,11 +199,10 @@ fn return_stmt_no_semicolon() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 20,
+                span: Span::new(FileId::default(), 20, 20),
                 ttype: TokenType::Eof,
-                lexeme: "".to_owned(),
-                found_in: "return_stmt_no_semicolon".to_owned()
+                lexeme: "".into(),
+                suffix: None,
             }),
             expected: TokenType::Semicolon,
             msg: None
@@ -176,11 +219,10 @@ fn return_expr_no_semicolon() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 12,
+                span: Span::new(FileId::default(), 12, 12),
                 ttype: TokenType::Eof,
-                lexeme: "".to_owned(),
-                found_in: "return_expr_no_semicolon".to_owned()
+                lexeme: "".into(),
+                suffix: None,
             }),
             expected: TokenType::Semicolon,
             msg: None
@@ -198,8 +240,7 @@ fn invalid_continue() {
         ParseError::UnexpectedToken {
             token, expected, ..
         } => {
-            assert_eq!(1, token.line);
-            assert_eq!(8, token.column);
+            assert_eq!(8, token.span.start);
             assert_eq!(TokenType::Eof, token.ttype);
             assert_eq!(TokenType::Semicolon, expected);
         }
@@ -213,7 +254,7 @@ fn valid_continue() {
     assert!(found.is_ok());
 
     match found.ok().unwrap() {
-        ScopeBoundStatement::Continue => {}
+        ScopeBoundStatement::Continue { .. } => {}
         _ => panic!(),
     }
 }
@@ -226,11 +267,10 @@ fn invalid_break() {
     assert_eq!(
         ParseError::UnexpectedToken {
             token: Box::new(Token {
-                line: 1,
-                column: 5,
+                span: Span::new(FileId::default(), 5, 5),
                 ttype: TokenType::Eof,
-                lexeme: "".to_owned(),
-                found_in: "invalid_break".to_owned()
+                lexeme: "".into(),
+                suffix: None,
             }),
             expected: TokenType::Semicolon,
             msg: None
@@ -245,7 +285,7 @@ fn valid_break() {
     assert!(found.is_ok());
 
     match found.ok().unwrap() {
-        ScopeBoundStatement::Break => {}
+        ScopeBoundStatement::Break { .. } => {}
         _ => panic!(),
     }
 }
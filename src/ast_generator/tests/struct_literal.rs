@@ -0,0 +1,95 @@
+use super::*;
+
+fn struct_literal_of(stmt: ScopeBoundStatement) -> (Token, Vec<(Token, Expression)>) {
+    match stmt {
+        ScopeBoundStatement::Expression {
+            expr: Expression::StructLiteral { name, fields, .. },
+            ..
+        } => (name, fields),
+        stmt => panic!("expected a struct literal, found {stmt:?}"),
+    }
+}
+
+#[test]
+fn struct_literal() {
+    let found = parse("struct_literal", "Point { x: 1, y: a + b };");
+    let (name, fields) = struct_literal_of(found.ok().unwrap());
+
+    assert_eq!("Point", name.lexeme);
+    assert_eq!(2, fields.len());
+    assert_eq!("x", fields[0].0.lexeme);
+    assert!(
+        matches!(fields[0].1, Expression::Literal { ref literal, .. } if literal.lexeme == "1")
+    );
+    assert_eq!("y", fields[1].0.lexeme);
+    assert!(matches!(fields[1].1, Expression::Binary { .. }));
+}
+
+#[test]
+fn field_shorthand() {
+    let found = parse("field_shorthand", "Point { x, y: 2, };");
+    let (_, fields) = struct_literal_of(found.ok().unwrap());
+
+    assert_eq!(2, fields.len());
+    assert!(matches!(fields[0].1, Expression::Name { ref name, .. } if name.lexeme == "x"));
+    assert!(matches!(fields[1].1, Expression::Literal { .. }));
+}
+
+#[test]
+fn empty_struct_literal() {
+    let found = parse("empty_struct_literal", "Unit {};");
+    let (name, fields) = struct_literal_of(found.ok().unwrap());
+
+    assert_eq!("Unit", name.lexeme);
+    assert!(fields.is_empty());
+}
+
+#[test]
+fn nested_struct_literal() {
+    let found = parse(
+        "nested_struct_literal",
+        "Line { from: Point { x, y }, to };",
+    );
+    let (_, fields) = struct_literal_of(found.ok().unwrap());
+
+    assert!(matches!(
+        fields[0].1,
+        Expression::StructLiteral { ref name, .. } if name.lexeme == "Point"
+    ));
+}
+
+#[test]
+fn condition_is_not_a_struct_literal() {
+    let found = parse("condition_is_not_a_struct_literal", "if x { y; }");
+
+    match found.ok().unwrap() {
+        ScopeBoundStatement::Conditional {
+            condition,
+            true_branch,
+            ..
+        } => {
+            assert!(matches!(condition, Expression::Name { ref name, .. } if name.lexeme == "x"));
+            assert_eq!(1, true_branch.len());
+        }
+        stmt => panic!("expected a conditional, found {stmt:?}"),
+    }
+
+    let found = parse(
+        "condition_is_not_a_struct_literal",
+        "while p == (Point { x, y }) { x; }",
+    );
+    assert!(matches!(
+        found.ok().unwrap(),
+        ScopeBoundStatement::While { body: Some(_), .. }
+    ));
+}
+
+#[test]
+fn malformed_struct_literal() {
+    for content in ["Point { x: };", "Point { 1: x };", "Point { x y };"] {
+        assert!(
+            parse("malformed_struct_literal", content).is_err(),
+            "`{content}` shouldn't parse"
+        );
+    }
+}
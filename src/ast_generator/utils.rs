@@ -1,8 +1,8 @@
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 
-use crate::tokens::error::ParseError;
+use crate::tokens::{error::ParseError, source::SourceFile, token::Token, token_type::TokenType};
 
-pub fn print_error(source: &str, after: &str, e: ParseError) {
+pub fn print_error(source: &SourceFile, after: &str, e: ParseError) {
     match e {
         ParseError::UnexpectedToken {
             token,
@@ -10,46 +10,34 @@ pub fn print_error(source: &str, after: &str, e: ParseError) {
             msg,
         } => {
             if let Some(msg) = msg {
-                eprintln!(
-                    "[{}] {msg}",
-                    format!("{source} {}:{}", token.line, token.column)
-                        .red()
-                        .bold()
-                );
+                eprintln!("[{}] {msg}", location(source, &token));
             } else {
                 eprintln!("[{}] :: there should have been a {} after the token `{after}`, but instead there was a {}.",
-                format!("{source} {}:{}", token.line, token.column).red().bold(),
+                location(source, &token),
                 format!("{expected}").blue().bold(),
-                format!("{}", token.found_in).red().italic());
+                format!("`{}`", token.lexeme).red().italic());
             }
         }
         ParseError::InvalidDataType { token, msg } => {
             if let Some(msg) = msg {
-                eprintln!(
-                    "[{}] {msg}",
-                    format!("{source} {}:{}", token.line, token.column)
-                        .red()
-                        .bold()
-                );
+                eprintln!("[{}] {msg}", location(source, &token));
             } else {
                 eprintln!(
                     "[{}] :: {} is not a valid data type.",
-                    format!("{source} {}:{}", token.line, token.column)
-                        .red()
-                        .bold(),
-                    format!("{}", token.found_in).red().italic()
+                    location(source, &token),
+                    format!("`{}`", token.lexeme).red().italic()
                 );
             }
         }
         ParseError::InvalidVariableDeclaration { token } => {
             eprintln!(
                 "[{}] :: You can create a variable using a dynamic definition `:=` followed by the value to assign to the variable, or by specifying the datatype statically. You cannot create a variable without assign it a value.",
-                format!("{source} {}:{}", token.line, token.column).red().bold()
+                location(source, &token)
             );
         }
         ParseError::LoopBodyNotFound { body } => {
             eprintln!("[{}] :: After a loop there must be either a scope block representing the body of the loop or a `;` for a loop without a body.",
-                format!("{} {}:{}", body.found_in, body.line, body.column).red().bold());
+                location(source, &body));
         }
         ParseError::InvalidAssignmentExpression {
             operation,
@@ -57,21 +45,14 @@ pub fn print_error(source: &str, after: &str, e: ParseError) {
         } => {
             eprintln!(
                 "[{}] :: Invalid assignment expression, can't assign a value to `{}`!",
-                format!(
-                    "{} {}:{}",
-                    operation.found_in, operation.line, operation.column
-                )
-                .red()
-                .bold(),
+                location(source, &operation),
                 assign_to
             );
         }
         ParseError::InvalidExpression { token } => {
             eprintln!(
                 "[{}] :: Invalid expression, RTFM!",
-                format!("{} {}:{}", token.found_in, token.line, token.column)
-                    .red()
-                    .bold()
+                location(source, &token)
             );
         }
         // ParseError::InvalidIterator { token, msg } => {
@@ -95,38 +76,60 @@ pub fn print_error(source: &str, after: &str, e: ParseError) {
         ParseError::InvalidFnName { name } => {
             eprintln!(
                 "[{}] :: {} is not a valid function name.",
-                format!("{} {}:{}", name.found_in, name.line, name.column)
-                    .red()
-                    .bold(),
+                location(source, &name),
                 format!("{} ({})", name.lexeme, name.ttype).red().italic()
             );
         }
         ParseError::InvalidFnBody { body } => {
             eprintln!(
                 "[{}] :: {} is not a valid function body.",
-                format!("{} {}:{}", body.found_in, body.line, body.column)
-                    .red()
-                    .bold(),
+                location(source, &body),
                 format!("{} ({})", body.lexeme, body.ttype).red().italic()
             );
         }
         ParseError::InvalidVariableAssignment { value } => {
             eprintln!(
                 "[{}] :: {} is not a value assignable to a variable.",
-                format!("{} {}:{}", value.found_in, value.line, value.column)
-                    .red()
-                    .bold(),
-                format!("{}", value.lexeme).red().italic()
+                location(source, &value),
+                value.lexeme.red().italic()
             );
         }
         ParseError::InvalidAddressOfValue { at } => {
             eprintln!(
                 "[{}] :: {} is not a at assignable to a variable.",
-                format!("{} {}:{}", at.found_in, at.line, at.column)
-                    .red()
-                    .bold(),
-                format!("{}", at.lexeme).red().italic()
+                location(source, &at),
+                at.lexeme.red().italic()
+            );
+        }
+        ParseError::DuplicateMatchArm { arm, first } => {
+            eprintln!(
+                "[{}] :: This match arm repeats the pattern of the arm at [{}], it can never be reached.",
+                location(source, &arm),
+                location(source, &first)
+            );
+        }
+        ParseError::UnreachableMatchArm { arm, covered_by } => {
+            eprintln!(
+                "[{}] :: This match arm can never be reached, the arm at [{}] already matches everything it does.",
+                location(source, &arm),
+                location(source, &covered_by)
             );
         }
     }
 }
+
+pub fn print_lex_error(source: &SourceFile, token: &Token) {
+    if let TokenType::Error(e) = &token.ttype {
+        eprintln!(
+            "[{}] :: Malformed token {}: {e}.",
+            location(source, token),
+            format!("`{}`", token.lexeme).red().italic()
+        );
+    }
+}
+
+/// Formats the `file line:column` prefix of the messages about `token`.
+fn location(source: &SourceFile, token: &Token) -> ColoredString {
+    let (line, column) = source.line_column(token.span.start);
+    format!("{} {line}:{column}", source.name()).red().bold()
+}
@@ -1,5 +1,9 @@
-use crate::ast_generator::ast::datatypes::DataType;
+use crate::{
+    ast_generator::ast::datatypes::DataType,
+    tokens::{source_map::Span, token_type::TokenType},
+};
 
+#[derive(Debug)]
 pub enum TypeError {
     InvalidReturnValue {
         line: usize,
@@ -29,4 +33,122 @@ pub enum TypeError {
         expected: DataType,
         found: DataType,
     },
+    NotFormattable {
+        line: usize,
+        column: usize,
+        found: DataType,
+    },
+    InvalidOperand {
+        span: Span,
+        operator: TokenType,
+        found: DataType,
+    },
+    NotIndexable {
+        span: Span,
+        found: DataType,
+    },
+    InvalidIndex {
+        span: Span,
+        found: DataType,
+    },
+    UnknownStruct {
+        span: Span,
+        name: String,
+    },
+    UnknownName {
+        span: Span,
+        name: String,
+    },
+    /// `name` refers to a function, a struct or an enum where a value is expected.
+    NotAValue {
+        span: Span,
+        name: String,
+        kind: &'static str,
+    },
+    InconsistentBindings {
+        span: Span,
+    },
+    UnknownEnum {
+        span: Span,
+        name: String,
+    },
+    UnknownVariant {
+        span: Span,
+        enumeration: String,
+        variant: String,
+    },
+    InvalidPayload {
+        span: Span,
+        variant: String,
+        expected: Option<DataType>,
+    },
+    UnknownField {
+        span: Span,
+        structure: String,
+        field: String,
+    },
+    DuplicateField {
+        span: Span,
+        structure: String,
+        field: String,
+    },
+    MissingFields {
+        span: Span,
+        structure: String,
+        fields: Vec<String>,
+    },
+    LiteralOutOfRange {
+        span: Span,
+        literal: String,
+        datatype: DataType,
+    },
+    /// Errors found together, like every wrong field of a struct literal.
+    Several(Vec<TypeError>),
+    /// The errors below are found in enum definitions, not in function bodies.
+    InvalidBacking {
+        span: Span,
+        backing: DataType,
+    },
+    DuplicateVariant {
+        span: Span,
+        first: Span,
+        variant: String,
+    },
+    InvalidDiscriminant {
+        span: Span,
+        variant: String,
+    },
+    /// The discriminant has a suffix other than the backing type.
+    DiscriminantSuffix {
+        span: Span,
+        variant: String,
+        suffix: DataType,
+        backing: DataType,
+    },
+    DiscriminantOutOfRange {
+        span: Span,
+        variant: String,
+        value: i128,
+        backing: DataType,
+    },
+    DuplicateDiscriminant {
+        span: Span,
+        variant: String,
+        value: i128,
+        taken_by: String,
+        first: Span,
+    },
+}
+
+impl TypeError {
+    /// The errors `self` stands for, `Several` is split into the errors it's made of.
+    pub fn into_errors(self) -> Vec<TypeError> {
+        match self {
+            TypeError::Several(errors) => errors
+                .into_iter()
+                .flat_map(TypeError::into_errors)
+                .collect(),
+            e => vec![e],
+        }
+    }
 }
@@ -7,6 +7,7 @@ use crate::ast_generator::ast::{
 use super::env::Environment;
 
 #[allow(dead_code)]
+#[derive(Clone)]
 pub enum Value<'a> {
     Function {
         arity: usize,
@@ -20,6 +21,22 @@ pub enum Value<'a> {
     Struct {
         ast: &'a Struct,
     },
+    /// A name bound inside a function body, e.g. by a match pattern.
+    Variable {
+        datatype: DataType,
+    },
+}
+
+impl Value<'_> {
+    /// What the name bound to this value is, as written in diagnostics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Function { .. } => "function",
+            Self::Enum { .. } => "enum",
+            Self::Struct { .. } => "struct",
+            Self::Variable { .. } => "variable",
+        }
+    }
 }
 
 impl Debug for Value<'_> {
@@ -32,6 +49,10 @@ impl Debug for Value<'_> {
                 .finish(),
             Self::Enum { .. } => f.debug_struct("Enum").finish(),
             Self::Struct { .. } => f.debug_struct("Struct").finish(),
+            Self::Variable { datatype } => f
+                .debug_struct("Variable")
+                .field("datatype", datatype)
+                .finish(),
         }
     }
 }
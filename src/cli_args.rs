@@ -1,6 +1,24 @@
-use clap::Parser as ClapParser;
+use ariel::tokens::dump::Format;
+use clap::{Parser as ClapParser, Subcommand};
 
 #[derive(ClapParser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct CliArgs {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path of the file to compile, `-` reads it from the standard input
     pub source: Option<String>,
 }
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print the tokens of a file with their line, column and byte span
+    Tokens {
+        /// Path of the file to lex, `-` reads it from the standard input
+        source: String,
+
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
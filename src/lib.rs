@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     sync::{Arc, Mutex},
 };
 
@@ -8,13 +8,23 @@ use ast_generator::ast::{
     scopebound_statements::ScopeBoundStatement, structs::Struct, ASTs,
 };
 use ast_walker::{env::Environment, type_error::TypeError, value::Value};
+use tokens::{
+    dump::{self, Format},
+    error::Error,
+    source::SourceFile,
+    source_map::{SourceMap, Span},
+    token::Token,
+};
 
 use crate::ast_generator::parser;
 
 mod ast_generator;
 mod ast_walker;
+#[cfg(test)]
 mod test_util;
-mod tokens;
+#[cfg(test)]
+mod tests;
+pub mod tokens;
 
 #[macro_export]
 macro_rules! measure {
@@ -34,8 +44,17 @@ macro_rules! measure {
 }
 
 pub fn compile(source: &str) {
-    let imported_files: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
-    let ast: ASTs = measure!(parser::parse(source, imported_files));
+    let source_map: Arc<Mutex<SourceMap>> = Arc::new(Mutex::new(SourceMap::new()));
+    let ast: ASTs = measure!(parser::parse(source, Arc::clone(&source_map)));
+
+    // every parser thread is done with the map by now
+    let source_map = match source_map.lock() {
+        Ok(map) => map,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
 
     let mut global_env: Environment = HashMap::new();
     let env_ptr: *mut Environment = &mut global_env;
@@ -48,12 +67,12 @@ pub fn compile(source: &str) {
                     return;
                 }
 
-                (*env_ptr).insert(&$ast2check.name.lexeme, $value2insert);
+                (*env_ptr).insert($ast2check.name.lexeme.as_str(), $value2insert);
             }
         };
     }
 
-    let _ = measure!({
+    measure!({
         ast.fns.iter().for_each(|func| {
             check_and_insert!(
                 func,
@@ -77,9 +96,9 @@ pub fn compile(source: &str) {
 
     println!("{global_env:#?}");
 
-    if !valid_structs(&global_env, &ast.structs)
-        | !valid_enums(&global_env, &ast.enums)
-        | !valid_fn(&global_env, &ast.fns)
+    if !valid_structs(&global_env, &source_map, &ast.structs)
+        | !valid_enums(&global_env, &source_map, &ast.enums)
+        | !valid_fn(&global_env, &source_map, &ast.fns)
     {
         return;
     }
@@ -87,36 +106,238 @@ pub fn compile(source: &str) {
     println!("All good!");
 }
 
-fn valid_enums(env: &Environment, enums: &[Enum]) -> bool {
+/// Prints the token stream of `source`, `-` reads it from the standard input.
+pub fn tokens(source: &str, format: Format) {
+    let source = match source {
+        "-" => SourceFile::from_stdin(),
+        path => SourceFile::new(path),
+    };
+
+    let mut source = match source {
+        Ok(source) => source,
+        Err(Error::FileNotFound(source, msg) | Error::MemoryMapFiled(source, msg)) => {
+            eprintln!("[{source}] :: {msg}");
+            return;
+        }
+        Err(Error::StdinUnreadable(msg)) => {
+            eprintln!("[<stdin>] :: {msg}");
+            return;
+        }
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    if let Err(e) = dump::dump(&mut source, format, &mut stdout) {
+        eprintln!("[{}] :: {e}", source.name());
+    }
+}
+
+fn valid_enums(env: &Environment, source_map: &SourceMap, enums: &[Enum]) -> bool {
     let mut res = true;
 
     for myenum in enums.iter() {
-        for (variant_name, datatype) in myenum.variants.iter() {
-            if let Some(dt) = datatype {
+        let file_name: &str = source_map.name(myenum.name.span.file);
+
+        for variant in myenum.variants.iter() {
+            if let Some(dt) = &variant.datatype {
                 if !valid_datatype(env, dt) {
                     res = false;
+                    let (line, column) = source_map.line_column(variant.name.span);
                     eprintln!(
-                        "[{} {}:{}] Enum `{}` defines field `{}` of type `{}` which doesn't exists.",
-                        myenum.name.found_in, variant_name.line, variant_name.column, myenum.name.lexeme, variant_name.lexeme, dt
+                        "[{file_name} {line}:{column}] Enum `{}` defines field `{}` of type `{}` which doesn't exists.",
+                        myenum.name.lexeme, variant.name.lexeme, dt
                     );
                 }
             }
         }
+
+        for e in evaluate_enum(myenum) {
+            res = false;
+            match e {
+                TypeError::InvalidBacking { span, backing } => {
+                    let (line, column) = source_map.line_column(span);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` is backed by `{backing}`, only integer types can back an enum.",
+                        myenum.name.lexeme
+                    );
+                }
+                TypeError::DuplicateVariant {
+                    span,
+                    first,
+                    variant,
+                } => {
+                    let (line, column) = source_map.line_column(span);
+                    let (first_line, first_column) = source_map.line_column(first);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` already defines variant `{variant}` at {first_line}:{first_column}.",
+                        myenum.name.lexeme
+                    );
+                }
+                TypeError::InvalidDiscriminant { span, variant } => {
+                    let (line, column) = source_map.line_column(span);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` gives variant `{variant}` a value which isn't an integer literal.",
+                        myenum.name.lexeme
+                    );
+                }
+                TypeError::DiscriminantSuffix {
+                    span,
+                    variant,
+                    suffix,
+                    backing,
+                } => {
+                    let (line, column) = source_map.line_column(span);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` is backed by `{backing}` but gives variant `{variant}` a value of type `{suffix}`.",
+                        myenum.name.lexeme
+                    );
+                }
+                TypeError::DiscriminantOutOfRange {
+                    span,
+                    variant,
+                    value,
+                    backing,
+                } => {
+                    let (line, column) = source_map.line_column(span);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` gives variant `{variant}` the value {value} which doesn't fit in `{backing}`.",
+                        myenum.name.lexeme
+                    );
+                }
+                TypeError::DuplicateDiscriminant {
+                    span,
+                    variant,
+                    value,
+                    taken_by,
+                    first,
+                } => {
+                    let (line, column) = source_map.line_column(span);
+                    let (first_line, first_column) = source_map.line_column(first);
+                    eprintln!(
+                        "[{file_name} {line}:{column}] Enum `{}` gives variant `{variant}` the value {value}, already taken by `{taken_by}` at {first_line}:{first_column}.",
+                        myenum.name.lexeme
+                    );
+                }
+                e => unreachable!("{e:?} isn't found in enum definitions"),
+            }
+        }
     }
 
     res
 }
 
-fn valid_structs(env: &Environment, structs: &[Struct]) -> bool {
+/// Checks the backing type of `myenum`, the names of its variants and their discriminants.
+fn evaluate_enum(myenum: &Enum) -> Vec<TypeError> {
+    let backing: &DataType = myenum.backing.as_ref().unwrap_or(&DataType::I32);
+    let Some((min, max)) = backing.integer_range() else {
+        return vec![TypeError::InvalidBacking {
+            span: myenum.name.span,
+            backing: backing.clone(),
+        }];
+    };
+
+    let mut errors: Vec<TypeError> = vec![];
+    // the discriminants given so far with the variant they belong to, a
+    // variant without an explicit one follows the previous variant
+    let mut taken: Vec<(i128, &Token)> = vec![];
+    let mut next: i128 = 0;
+    for (index, variant) in myenum.variants.iter().enumerate() {
+        if let Some(first) = myenum.variants[..index]
+            .iter()
+            .find(|other| other.name.lexeme == variant.name.lexeme)
+        {
+            errors.push(TypeError::DuplicateVariant {
+                span: variant.name.span,
+                first: first.name.span,
+                variant: variant.name.lexeme.to_string(),
+            });
+        }
+
+        let value: i128 = match &variant.value {
+            None => next,
+            Some(value) => match discriminant(value) {
+                Some((value, literal)) => {
+                    let suffix: Option<DataType> =
+                        literal.suffix.as_ref().and_then(DataType::from_primitive);
+                    if let Some(suffix) = suffix.filter(|suffix| suffix != backing) {
+                        errors.push(TypeError::DiscriminantSuffix {
+                            span: literal.span,
+                            variant: variant.name.lexeme.to_string(),
+                            suffix,
+                            backing: backing.clone(),
+                        });
+                    }
+
+                    value
+                }
+                None => {
+                    errors.push(TypeError::InvalidDiscriminant {
+                        span: variant.name.span,
+                        variant: variant.name.lexeme.to_string(),
+                    });
+                    continue;
+                }
+            },
+        };
+        next = value + 1;
+
+        if value < min || max < value {
+            errors.push(TypeError::DiscriminantOutOfRange {
+                span: variant.name.span,
+                variant: variant.name.lexeme.to_string(),
+                value,
+                backing: backing.clone(),
+            });
+            continue;
+        }
+
+        match taken.iter().find(|(other, _)| *other == value) {
+            Some((_, other)) => errors.push(TypeError::DuplicateDiscriminant {
+                span: variant.name.span,
+                variant: variant.name.lexeme.to_string(),
+                value,
+                taken_by: other.lexeme.to_string(),
+                first: other.span,
+            }),
+            None => taken.push((value, &variant.name)),
+        }
+    }
+
+    errors
+}
+
+/// The value of an explicit enum discriminant, an integer literal which may be
+/// negated, along with the literal.
+fn discriminant(value: &expressions::Expression) -> Option<(i128, &Token)> {
+    match value {
+        expressions::Expression::Literal { literal, .. }
+            if literal.ttype == tokens::token_type::TokenType::Integer =>
+        {
+            let value: u64 = literal.lexeme.parse().ok()?;
+            Some((i128::from(value), literal))
+        }
+        expressions::Expression::Unary {
+            operation, value, ..
+        } if operation.ttype == tokens::token_type::TokenType::Minus => {
+            discriminant(value).map(|(value, literal)| (-value, literal))
+        }
+        _ => None,
+    }
+}
+
+fn valid_structs(env: &Environment, source_map: &SourceMap, structs: &[Struct]) -> bool {
     let mut res = true;
 
     for mystruct in structs.iter() {
-        for (fieldname, datatype) in mystruct.fields.iter() {
-            if !valid_datatype(env, datatype) {
+        for field in mystruct.fields.iter() {
+            if !valid_datatype(env, &field.datatype) {
                 res = false;
+                let (line, column) = source_map.line_column(field.name.span);
                 eprintln!(
-                    "[{} {}:{}] Struct `{}` defines field `{}` of type `{datatype}` which doesn't exists.",
-                    mystruct.name.found_in, fieldname.line, fieldname.column, mystruct.name.lexeme, fieldname.lexeme
+                    "[{} {line}:{column}] Struct `{}` defines field `{}` of type `{}` which doesn't exists.",
+                    source_map.name(mystruct.name.span.file),
+                    mystruct.name.lexeme,
+                    field.name.lexeme,
+                    field.datatype
                 );
             }
         }
@@ -125,16 +346,19 @@ fn valid_structs(env: &Environment, structs: &[Struct]) -> bool {
     res
 }
 
-fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
+fn valid_fn(env: &Environment, source_map: &SourceMap, fns: &[Function]) -> bool {
     let mut res = true;
 
     for myfn in fns.iter() {
+        let file_name: &str = source_map.name(myfn.name.span.file);
+
         for (arg, datatype) in myfn.args.iter() {
             if !valid_datatype(env, datatype) {
                 res = false;
+                let (line, column) = source_map.line_column(arg.span);
                 eprintln!(
-                    "[{} {}:{}] Function `{}` expects argument `{}` of type `{datatype}` which doesn't exists.",
-                    myfn.name.found_in, arg.line, arg.column, myfn.name.lexeme, arg.lexeme
+                    "[{file_name} {line}:{column}] Function `{}` expects argument `{}` of type `{datatype}` which doesn't exists.",
+                    myfn.name.lexeme, arg.lexeme
                 );
             }
         }
@@ -142,15 +366,26 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
         if let Some(datatype) = &myfn.ret_type {
             if !valid_datatype(env, datatype) {
                 res = false;
+                let (line, column) = source_map.line_column(myfn.name.span);
                 eprintln!(
-                    "[{} {}:{}] Function `{}` returns `{datatype}` but this type isn't defined.",
-                    myfn.name.found_in, myfn.name.line, myfn.name.column, myfn.name.lexeme
+                    "[{file_name} {line}:{column}] Function `{}` returns `{datatype}` but this type isn't defined.",
+                    myfn.name.lexeme
                 );
             }
         }
 
         if let Some(body) = &myfn.body {
-            if let Err(evec) = validate_local_scope(body, &myfn.ret_type) {
+            let mut fn_env: Environment = env.clone();
+            fn_env.extend(myfn.args.iter().map(|(arg, datatype)| {
+                (
+                    arg.lexeme.as_str(),
+                    Value::Variable {
+                        datatype: datatype.clone(),
+                    },
+                )
+            }));
+
+            if let Err(evec) = validate_local_scope(&fn_env, body, &myfn.ret_type) {
                 for e in evec {
                     res = false;
                     match e {
@@ -162,7 +397,7 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
                         } => {
                             eprintln!(
                         "[{} {}:{}] Invalid return type in function {}: expected `{expected}` but instead received `{got}`.",
-                        myfn.name.found_in, line, column, myfn.name.lexeme
+                        file_name, line, column, myfn.name.lexeme
                     );
                         }
                         TypeError::UnexpectedType {
@@ -173,13 +408,13 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
                         } => {
                             eprintln!(
                         "[{} {}:{}] Unexpected type in function {}: expected `{expected}` but instead received `{got}`.",
-                        myfn.name.found_in, line, column, myfn.name.lexeme
+                        file_name, line, column, myfn.name.lexeme
                     );
                         }
                         TypeError::InvalidStmt { line, column } => {
                             eprintln!(
                                 "[{} {}:{}] Impossible statement in function {}.",
-                                myfn.name.found_in, line, column, myfn.name.lexeme
+                                file_name, line, column, myfn.name.lexeme
                             );
                         }
                         TypeError::InvalidTypeConversion {
@@ -190,7 +425,150 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
                         } => {
                             eprintln!(
                             "[{} {}:{}] Invalid type conversion in function {}: from value of type `{from}` to value of type `{to}`.",
-                            myfn.name.found_in, line, column, myfn.name.lexeme
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::NotFormattable {
+                            line,
+                            column,
+                            found,
+                        } => {
+                            eprintln!(
+                            "[{} {}:{}] Invalid interpolation in function {}: a value of type `{found}` can't be formatted into a string.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::InvalidOperand {
+                            span,
+                            operator,
+                            found,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Invalid operand in function {}: `{operator}` can't be applied to a value of type `{found}`.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::NotIndexable { span, found } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Invalid indexing in function {}: a value of type `{found}` can't be indexed, only arrays, pointers and strings can.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::InvalidIndex { span, found } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Invalid index in function {}: an index must be an integer but it's a value of type `{found}`.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::UnknownName { span, name } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                                "[{} {}:{}] Unknown name in function {}: `{name}` isn't defined.",
+                                file_name, line, column, myfn.name.lexeme
+                            );
+                        }
+                        TypeError::NotAValue { span, name, kind } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                                "[{} {}:{}] Invalid value in function {}: `{name}` is a {kind}, not a value.",
+                                file_name, line, column, myfn.name.lexeme
+                            );
+                        }
+                        TypeError::InconsistentBindings { span } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Invalid or-pattern in function {}: every alternative must bind the same names to values of the same types.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::UnknownEnum { span, name } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                                "[{} {}:{}] Unknown enum in function {}: `{name}` isn't an enum.",
+                                file_name, line, column, myfn.name.lexeme
+                            );
+                        }
+                        TypeError::UnknownVariant {
+                            span,
+                            enumeration,
+                            variant,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Unknown variant in function {}: enum `{enumeration}` has no variant `{variant}`.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::InvalidPayload {
+                            span,
+                            variant,
+                            expected,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            match expected {
+                                Some(expected) => eprintln!(
+                                "[{} {}:{}] Invalid payload in function {}: variant `{variant}` carries a value of type `{expected}`.",
+                                file_name, line, column, myfn.name.lexeme
+                            ),
+                                None => eprintln!(
+                                "[{} {}:{}] Invalid payload in function {}: variant `{variant}` doesn't carry a value.",
+                                file_name, line, column, myfn.name.lexeme
+                            ),
+                            }
+                        }
+                        TypeError::UnknownStruct { span, name } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Unknown struct in function {}: `{name}` isn't a struct.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::UnknownField {
+                            span,
+                            structure,
+                            field,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Unknown field in function {}: struct `{structure}` has no field `{field}`.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::DuplicateField {
+                            span,
+                            structure,
+                            field,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Duplicate field in function {}: field `{field}` of struct `{structure}` is given more than once.",
+                            file_name, line, column, myfn.name.lexeme
+                        );
+                        }
+                        TypeError::MissingFields {
+                            span,
+                            structure,
+                            fields,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Missing fields in function {}: struct `{structure}` also needs {}.",
+                            file_name, line, column, myfn.name.lexeme,
+                            fields.iter().map(|field| format!("`{field}`")).collect::<Vec<String>>().join(", ")
+                        );
+                        }
+                        TypeError::LiteralOutOfRange {
+                            span,
+                            literal,
+                            datatype,
+                        } => {
+                            let (line, column) = source_map.line_column(span);
+                            eprintln!(
+                            "[{} {}:{}] Literal out of range in function {}: `{literal}` doesn't fit in a value of type `{datatype}`.",
+                            file_name, line, column, myfn.name.lexeme
                         );
                         }
                         TypeError::InvalidArrayLiteral {
@@ -201,9 +579,20 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
                         } => {
                             eprintln!(
                             "[{} {}:{}] Invalid array literal in function {}: an array can contain only expression that result to the same type but there was a mix of `{expected}` and `{found}` types.",
-                            myfn.name.found_in, line, column, myfn.name.lexeme
+                            file_name, line, column, myfn.name.lexeme
                         );
                         }
+                        TypeError::InvalidBacking { .. }
+                        | TypeError::DuplicateVariant { .. }
+                        | TypeError::InvalidDiscriminant { .. }
+                        | TypeError::DiscriminantSuffix { .. }
+                        | TypeError::DiscriminantOutOfRange { .. }
+                        | TypeError::DuplicateDiscriminant { .. } => {
+                            unreachable!("enum definitions are checked by `valid_enums`")
+                        }
+                        TypeError::Several(_) => {
+                            unreachable!("`validate_local_scope` splits the errors found together")
+                        }
                     }
                 }
             }
@@ -214,6 +603,7 @@ fn valid_fn(env: &Environment, fns: &[Function]) -> bool {
 }
 
 fn validate_local_scope(
+    env: &Environment,
     stmts: &[ScopeBoundStatement],
     return_type: &Option<DataType>,
 ) -> Result<(), Vec<TypeError>> {
@@ -221,7 +611,7 @@ fn validate_local_scope(
     for stmt in stmts.iter() {
         match stmt {
             ScopeBoundStatement::Scope { body, .. } => {
-                if let Err(mut sub_errvec) = validate_local_scope(&body, return_type) {
+                if let Err(mut sub_errvec) = validate_local_scope(env, body, return_type) {
                     errvec.append(&mut sub_errvec);
                 }
             }
@@ -232,7 +622,8 @@ fn validate_local_scope(
                 column,
             } => match maybe_value {
                 Some(value) => {
-                    let maybe_found_type: Result<DataType, TypeError> = evaluate(value);
+                    let maybe_found_type: Result<DataType, TypeError> =
+                        evaluate(env, value, return_type.as_ref());
                     match maybe_found_type {
                         Ok(found_type) => match return_type {
                             Some(expected_type) => {
@@ -272,7 +663,46 @@ fn validate_local_scope(
             ScopeBoundStatement::Expression { .. } => todo!(),
             ScopeBoundStatement::Defer { .. } => todo!(),
             ScopeBoundStatement::Conditional { .. } => todo!(),
-            ScopeBoundStatement::Match { .. } => todo!(),
+            ScopeBoundStatement::Match { on, cases, .. } => match evaluate_expr(env, on, None) {
+                Ok(on_type) => {
+                    for (case, body) in cases.iter() {
+                        let mut bindings: Vec<(&str, DataType)> = vec![];
+                        if let Err(e) =
+                            evaluate_pattern(env, &case.pattern, &on_type, &mut bindings)
+                        {
+                            errvec.push(e);
+                            continue;
+                        }
+
+                        let mut case_env: Environment = env.clone();
+                        case_env.extend(
+                            bindings
+                                .into_iter()
+                                .map(|(name, datatype)| (name, Value::Variable { datatype })),
+                        );
+
+                        if let Some(guard) = &case.guard {
+                            match evaluate_expr(&case_env, guard, Some(&DataType::Bool)) {
+                                Ok(DataType::Bool) => {}
+                                Ok(guard_type) => errvec.push(TypeError::UnexpectedType {
+                                    line: guard.line(),
+                                    column: guard.column(),
+                                    expected: DataType::Bool,
+                                    got: guard_type,
+                                }),
+                                Err(e) => errvec.push(e),
+                            }
+                        }
+
+                        if let Err(mut sub_errvec) =
+                            validate_local_scope(&case_env, body, return_type)
+                        {
+                            errvec.append(&mut sub_errvec);
+                        }
+                    }
+                }
+                Err(e) => errvec.push(e),
+            },
             ScopeBoundStatement::Loop {
                 line: _,
                 column: _,
@@ -290,7 +720,7 @@ fn validate_local_scope(
                 line,
                 column,
             } => {
-                let maybe_condition_type = evaluate_expr(condition);
+                let maybe_condition_type = evaluate_expr(env, condition, Some(&DataType::Bool));
                 match maybe_condition_type {
                     Ok(condition_type) => {
                         if condition_type != DataType::Bool {
@@ -319,7 +749,10 @@ fn validate_local_scope(
     if errvec.is_empty() {
         Ok(())
     } else {
-        Err(errvec)
+        Err(errvec
+            .into_iter()
+            .flat_map(TypeError::into_errors)
+            .collect())
     }
 }
 
@@ -331,7 +764,11 @@ fn valid_datatype(env: &Environment, datatype: &DataType) -> bool {
     }
 }
 
-fn evaluate(value: &ScopeBoundStatement) -> Result<DataType, TypeError> {
+fn evaluate(
+    env: &Environment,
+    value: &ScopeBoundStatement,
+    expected: Option<&DataType>,
+) -> Result<DataType, TypeError> {
     let mut returns = DataType::Void;
     match value {
         ScopeBoundStatement::Scope { body, .. } => {
@@ -339,12 +776,12 @@ fn evaluate(value: &ScopeBoundStatement) -> Result<DataType, TypeError> {
                 match stmt {
                     ScopeBoundStatement::Scope { .. } => todo!(),
                     ScopeBoundStatement::Return { value, .. } => match value {
-                        Some(value) => returns = evaluate(&value)?,
+                        Some(value) => returns = evaluate(env, value, expected)?,
                         None => returns = DataType::Void,
                     },
                     ScopeBoundStatement::ImplicitReturn { expr, .. }
                     | ScopeBoundStatement::Expression { expr, .. } => {
-                        returns = evaluate_expr(&expr)?
+                        returns = evaluate_expr(env, expr, expected)?
                     }
                     ScopeBoundStatement::Defer { .. } => todo!(),
                     ScopeBoundStatement::Conditional { .. } => todo!(),
@@ -359,7 +796,9 @@ fn evaluate(value: &ScopeBoundStatement) -> Result<DataType, TypeError> {
         ScopeBoundStatement::VariableDeclaration { .. } => todo!(),
         ScopeBoundStatement::Return { .. } => todo!(),
         ScopeBoundStatement::ImplicitReturn { .. } => todo!(),
-        ScopeBoundStatement::Expression { expr, .. } => returns = evaluate_expr(expr)?,
+        ScopeBoundStatement::Expression { expr, .. } => {
+            returns = evaluate_expr(env, expr, expected)?
+        }
         ScopeBoundStatement::Defer { .. } => todo!(),
         ScopeBoundStatement::Conditional { .. } => todo!(),
         ScopeBoundStatement::Match { .. } => todo!(),
@@ -373,11 +812,22 @@ fn evaluate(value: &ScopeBoundStatement) -> Result<DataType, TypeError> {
     Ok(returns)
 }
 
-fn evaluate_expr(expr: &expressions::Expression) -> Result<DataType, TypeError> {
+fn evaluate_expr(
+    env: &Environment,
+    expr: &expressions::Expression,
+    expected: Option<&DataType>,
+) -> Result<DataType, TypeError> {
     match expr {
         expressions::Expression::Literal { literal, .. } => match literal.ttype {
-            tokens::token_type::TokenType::Double => Ok(DataType::F32),
-            tokens::token_type::TokenType::Integer => Ok(DataType::I32),
+            tokens::token_type::TokenType::Double => match (&literal.suffix, expected) {
+                (Some(suffix), _) => Ok(DataType::from_primitive(suffix).unwrap()),
+                (None, Some(expected)) if expected.is_float() => Ok(expected.clone()),
+                (None, _) => Ok(DataType::F32),
+            },
+            tokens::token_type::TokenType::Integer => {
+                evaluate_integer_literal(literal, expected, false)
+            }
+            tokens::token_type::TokenType::Char => Ok(DataType::Char),
             tokens::token_type::TokenType::String => Ok(DataType::String),
             tokens::token_type::TokenType::True | tokens::token_type::TokenType::False => {
                 Ok(DataType::Bool)
@@ -389,14 +839,18 @@ fn evaluate_expr(expr: &expressions::Expression) -> Result<DataType, TypeError>
             if values.is_empty() {
                 Ok(DataType::Void)
             } else {
-                let res: DataType = evaluate_expr(&values[0])?;
+                let element_type: Option<&DataType> = match expected {
+                    Some(DataType::Array(of)) => Some(of),
+                    _ => None,
+                };
+                let res: DataType = evaluate_expr(env, &values[0], element_type)?;
 
-                for x in 1..values.len() {
-                    let curr_dt = evaluate_expr(&values[x])?;
+                for value in values.iter().skip(1) {
+                    let curr_dt = evaluate_expr(env, value, element_type)?;
                     if curr_dt != res {
                         return Err(TypeError::InvalidArrayLiteral {
-                            line: values[x].line(),
-                            column: values[x].column(),
+                            line: value.line(),
+                            column: value.column(),
                             expected: res,
                             found: curr_dt,
                         });
@@ -406,6 +860,627 @@ fn evaluate_expr(expr: &expressions::Expression) -> Result<DataType, TypeError>
                 Ok(DataType::Array(Box::new(res)))
             }
         }
+        expressions::Expression::Binary {
+            left,
+            operation,
+            right,
+            ..
+        } => match operation.ttype {
+            tokens::token_type::TokenType::EqualEqual
+            | tokens::token_type::TokenType::NotEqual
+            | tokens::token_type::TokenType::Less
+            | tokens::token_type::TokenType::LessEqual
+            | tokens::token_type::TokenType::Greater
+            | tokens::token_type::TokenType::GreaterEqual => {
+                let (left_type, right_type) = evaluate_operands(env, left, right)?;
+
+                if left_type != right_type {
+                    return Err(TypeError::UnexpectedType {
+                        line: right.line(),
+                        column: right.column(),
+                        expected: left_type,
+                        got: right_type,
+                    });
+                }
+
+                Ok(DataType::Bool)
+            }
+            tokens::token_type::TokenType::Equal => {
+                let left_type: DataType = evaluate_expr(env, left, None)?;
+                let right_type: DataType = evaluate_expr(env, right, Some(&left_type))?;
+
+                if left_type != right_type {
+                    return Err(TypeError::UnexpectedType {
+                        line: right.line(),
+                        column: right.column(),
+                        expected: left_type,
+                        got: right_type,
+                    });
+                }
+
+                Ok(left_type)
+            }
+            ref ttype => {
+                // `a op= b` is checked as `a op b`, assigning it back to `a` keeps its type
+                let operator = ttype.compound_operator().unwrap_or_else(|| ttype.clone());
+                let keeps_left_type: bool = ttype.compound_operator().is_some()
+                    || matches!(
+                        operator,
+                        tokens::token_type::TokenType::ShiftLeft
+                            | tokens::token_type::TokenType::ShiftRight
+                    );
+                // what's assigned to or shifted keeps its type whatever the other side is
+                let (left_type, right_type) = if keeps_left_type {
+                    let left_type: DataType = evaluate_expr(env, left, None)?;
+                    let right_type: DataType = evaluate_expr(env, right, Some(&left_type))?;
+                    (left_type, right_type)
+                } else {
+                    evaluate_operands(env, left, right)?
+                };
+
+                evaluate_binary_operation(
+                    (operator, operation.span),
+                    left_type,
+                    (right, right_type),
+                )
+            }
+        },
+        expressions::Expression::Unary {
+            operation, value, ..
+        } => {
+            let value_type: DataType = match (&operation.ttype, &**value) {
+                (tokens::token_type::TokenType::Not, _) => evaluate_expr(env, value, None)?,
+                // `-128i8` fits even though `128i8` doesn't
+                (
+                    tokens::token_type::TokenType::Minus,
+                    expressions::Expression::Literal { literal, .. },
+                ) if literal.ttype == tokens::token_type::TokenType::Integer => {
+                    evaluate_integer_literal(literal, expected, true)?
+                }
+                _ => evaluate_expr(env, value, expected)?,
+            };
+
+            let valid: bool = match operation.ttype {
+                tokens::token_type::TokenType::Not => value_type == DataType::Bool,
+                tokens::token_type::TokenType::Minus => value_type.is_signed(),
+                tokens::token_type::TokenType::BitNot => value_type.is_integer(),
+                _ => panic!("somehow `{}` is used as a prefix operator", operation.ttype),
+            };
+
+            if valid {
+                Ok(value_type)
+            } else {
+                Err(TypeError::InvalidOperand {
+                    span: operation.span,
+                    operator: operation.ttype.clone(),
+                    found: value_type,
+                })
+            }
+        }
+        expressions::Expression::Cast { value, to, .. } => {
+            // a literal is taken as a value of the type it's cast to, `65 as char`
+            // is a `u8` turned into a `char`
+            let value_expected: Option<&DataType> = match (&**value, to) {
+                (expressions::Expression::Literal { .. }, DataType::Char) => Some(&DataType::U8),
+                (expressions::Expression::Literal { .. }, to) => Some(to),
+                _ => None,
+            };
+            let from: DataType = evaluate_expr(env, value, value_expected)?;
+
+            if can_cast(env, &from, to) {
+                Ok(to.clone())
+            } else {
+                Err(TypeError::InvalidTypeConversion {
+                    line: value.line(),
+                    column: value.column(),
+                    from,
+                    to: to.clone(),
+                })
+            }
+        }
+        expressions::Expression::Interpolation { holes, .. } => {
+            for hole in holes.iter() {
+                let hole_type: DataType = evaluate_expr(env, hole, None)?;
+                if !hole_type.is_formattable() {
+                    return Err(TypeError::NotFormattable {
+                        line: hole.line(),
+                        column: hole.column(),
+                        found: hole_type,
+                    });
+                }
+            }
+
+            Ok(DataType::String)
+        }
+        expressions::Expression::Index {
+            span, from, index, ..
+        } => {
+            // an array literal adapts its elements to the type expected of the one picked
+            let from_expected: Option<DataType> =
+                expected.map(|element| DataType::Array(Box::new(element.clone())));
+            let from_type: DataType = evaluate_expr(env, from, from_expected.as_ref())?;
+            evaluate_index(env, index, *span)?;
+
+            match from_type {
+                DataType::Array(of) | DataType::Pointer(of) => Ok(*of),
+                DataType::String => Ok(DataType::Char),
+                found => Err(TypeError::NotIndexable { span: *span, found }),
+            }
+        }
+        expressions::Expression::Slice {
+            span,
+            from,
+            start,
+            end,
+            ..
+        } => {
+            let from_type: DataType = evaluate_expr(env, from, expected)?;
+            for bound in [start, end].into_iter().flatten() {
+                evaluate_index(env, bound, *span)?;
+            }
+
+            match from_type {
+                DataType::Array(of) | DataType::Pointer(of) => Ok(DataType::Array(of)),
+                DataType::String => Ok(DataType::String),
+                found => Err(TypeError::NotIndexable { span: *span, found }),
+            }
+        }
+        expressions::Expression::Name { name, .. } => match env.get(name.lexeme.as_str()) {
+            Some(Value::Variable { datatype }) => Ok(datatype.clone()),
+            Some(value) => Err(TypeError::NotAValue {
+                span: name.span,
+                name: name.lexeme.to_string(),
+                kind: value.kind(),
+            }),
+            None => Err(TypeError::UnknownName {
+                span: name.span,
+                name: name.lexeme.to_string(),
+            }),
+        },
+        expressions::Expression::EnumVariant {
+            enumeration,
+            variant,
+            payload,
+            ..
+        } => {
+            let payload_type: Option<&DataType> = evaluate_variant(env, enumeration, variant)?;
+
+            match (payload, payload_type) {
+                (None, None) => {}
+                (Some(payload), Some(payload_type)) => {
+                    let found: DataType = evaluate_expr(env, payload, Some(payload_type))?;
+                    if found != *payload_type {
+                        return Err(TypeError::UnexpectedType {
+                            line: payload.line(),
+                            column: payload.column(),
+                            expected: payload_type.clone(),
+                            got: found,
+                        });
+                    }
+                }
+                (_, payload_type) => {
+                    return Err(TypeError::InvalidPayload {
+                        span: variant.span,
+                        variant: variant.lexeme.to_string(),
+                        expected: payload_type.cloned(),
+                    })
+                }
+            }
+
+            Ok(DataType::Compound {
+                name: Box::new(enumeration.clone()),
+            })
+        }
+        expressions::Expression::StructLiteral { name, fields, .. } => {
+            let ast: &Struct = match env.get(name.lexeme.as_str()) {
+                Some(Value::Struct { ast }) => ast,
+                _ => {
+                    return Err(TypeError::UnknownStruct {
+                        span: name.span,
+                        name: name.lexeme.to_string(),
+                    })
+                }
+            };
+
+            let mut errors: Vec<TypeError> = vec![];
+            for (index, (field, value)) in fields.iter().enumerate() {
+                let declared = match ast.fields.iter().find(|f| f.name.lexeme == field.lexeme) {
+                    Some(declared) => declared,
+                    None => {
+                        errors.push(TypeError::UnknownField {
+                            span: field.span,
+                            structure: name.lexeme.to_string(),
+                            field: field.lexeme.to_string(),
+                        });
+                        continue;
+                    }
+                };
+
+                if fields[..index]
+                    .iter()
+                    .any(|(previous, _)| previous.lexeme == field.lexeme)
+                {
+                    errors.push(TypeError::DuplicateField {
+                        span: field.span,
+                        structure: name.lexeme.to_string(),
+                        field: field.lexeme.to_string(),
+                    });
+                    continue;
+                }
+
+                match evaluate_expr(env, value, Some(&declared.datatype)) {
+                    Ok(value_type) if value_type == declared.datatype => {}
+                    Ok(value_type) => errors.push(TypeError::UnexpectedType {
+                        line: value.line(),
+                        column: value.column(),
+                        expected: declared.datatype.clone(),
+                        got: value_type,
+                    }),
+                    Err(e) => errors.push(e),
+                }
+            }
+
+            let missing: Vec<String> = ast
+                .fields
+                .iter()
+                .filter(|declared| {
+                    !fields
+                        .iter()
+                        .any(|(field, _)| field.lexeme == declared.name.lexeme)
+                })
+                .map(|declared| declared.name.lexeme.to_string())
+                .collect();
+            if !missing.is_empty() {
+                errors.push(TypeError::MissingFields {
+                    span: name.span,
+                    structure: name.lexeme.to_string(),
+                    fields: missing,
+                });
+            }
+
+            match errors.len() {
+                0 => {}
+                1 => return Err(errors.remove(0)),
+                _ => return Err(TypeError::Several(errors)),
+            }
+
+            Ok(DataType::Compound {
+                name: Box::new(ast.name.clone()),
+            })
+        }
+        expressions::Expression::Nested { nested, .. } => evaluate_expr(env, nested, expected),
         _ => todo!(),
     }
 }
+
+/// Looks `variant` up in `enumeration`, giving back the type of its payload if it carries one.
+fn evaluate_variant<'a>(
+    env: &'a Environment,
+    enumeration: &Token,
+    variant: &Token,
+) -> Result<Option<&'a DataType>, TypeError> {
+    let ast: &Enum = match env.get(enumeration.lexeme.as_str()) {
+        Some(Value::Enum { ast }) => ast,
+        _ => {
+            return Err(TypeError::UnknownEnum {
+                span: enumeration.span,
+                name: enumeration.lexeme.to_string(),
+            })
+        }
+    };
+
+    match ast
+        .variants
+        .iter()
+        .find(|found| found.name.lexeme == variant.lexeme)
+    {
+        Some(found) => Ok(found.datatype.as_ref()),
+        None => Err(TypeError::UnknownVariant {
+            span: variant.span,
+            enumeration: enumeration.lexeme.to_string(),
+            variant: variant.lexeme.to_string(),
+        }),
+    }
+}
+
+/// Checks that `pattern` can match a value of type `expected`, the names it
+/// binds are pushed to `bindings` together with their type.
+fn evaluate_pattern<'p>(
+    env: &Environment,
+    pattern: &'p expressions::Expression,
+    expected: &DataType,
+    bindings: &mut Vec<(&'p str, DataType)>,
+) -> Result<(), TypeError> {
+    match pattern {
+        expressions::Expression::Name { name, .. } => {
+            if name.ttype != tokens::token_type::TokenType::DontCare {
+                bindings.push((name.lexeme.as_str(), expected.clone()));
+            }
+
+            Ok(())
+        }
+        expressions::Expression::Sequence {
+            line,
+            column,
+            span,
+            start,
+            end,
+        } => {
+            if !expected.is_integer() {
+                // the range on its own is of the first of these types holding both bounds
+                let got: DataType = [DataType::I32, DataType::I64, DataType::U64]
+                    .into_iter()
+                    .find(|datatype| {
+                        datatype
+                            .integer_range()
+                            .is_some_and(|(_, max)| *start <= max && *end <= max)
+                    })
+                    .unwrap_or(DataType::U64);
+
+                return Err(TypeError::UnexpectedType {
+                    line: *line,
+                    column: *column,
+                    expected: expected.clone(),
+                    got,
+                });
+            }
+
+            // an exclusive range up to 0 ends at -1, it's empty and only its start matters
+            for bound in [*start, *end]
+                .into_iter()
+                .filter_map(|bound| u64::try_from(bound).ok())
+            {
+                if !literal_fits(bound, expected, false) {
+                    return Err(TypeError::LiteralOutOfRange {
+                        span: *span,
+                        literal: bound.to_string(),
+                        datatype: expected.clone(),
+                    });
+                }
+            }
+
+            Ok(())
+        }
+        expressions::Expression::EnumVariant {
+            line,
+            column,
+            enumeration,
+            variant,
+            payload,
+        } => {
+            let is_expected_enum: bool = matches!(
+                expected,
+                DataType::Compound { name } if name.lexeme == enumeration.lexeme
+            );
+            if !is_expected_enum {
+                return Err(TypeError::UnexpectedType {
+                    line: *line,
+                    column: *column,
+                    expected: expected.clone(),
+                    got: DataType::Compound {
+                        name: Box::new(enumeration.clone()),
+                    },
+                });
+            }
+
+            match (payload, evaluate_variant(env, enumeration, variant)?) {
+                (None, None) => Ok(()),
+                (Some(payload), Some(payload_type)) => {
+                    evaluate_pattern(env, payload, payload_type, bindings)
+                }
+                (_, payload_type) => Err(TypeError::InvalidPayload {
+                    span: variant.span,
+                    variant: variant.lexeme.to_string(),
+                    expected: payload_type.cloned(),
+                }),
+            }
+        }
+        expressions::Expression::Alternatives { span, patterns, .. } => {
+            // every alternative has to bind the same names to the same types
+            let mut first_bindings: Option<Vec<(&'p str, DataType)>> = None;
+            for pattern in patterns.iter() {
+                let mut pattern_bindings: Vec<(&'p str, DataType)> = vec![];
+                evaluate_pattern(env, pattern, expected, &mut pattern_bindings)?;
+                pattern_bindings.sort_by_key(|(name, _)| *name);
+
+                match &first_bindings {
+                    Some(first) if *first != pattern_bindings => {
+                        return Err(TypeError::InconsistentBindings { span: *span })
+                    }
+                    Some(_) => {}
+                    None => first_bindings = Some(pattern_bindings),
+                }
+            }
+
+            bindings.extend(first_bindings.unwrap_or_default());
+            Ok(())
+        }
+        literal => {
+            let found: DataType = evaluate_expr(env, literal, Some(expected))?;
+            if found == *expected {
+                Ok(())
+            } else {
+                Err(TypeError::UnexpectedType {
+                    line: literal.line(),
+                    column: literal.column(),
+                    expected: expected.clone(),
+                    got: found,
+                })
+            }
+        }
+    }
+}
+
+/// The conversions `as` allows: between numbers, from a `char` to an integer
+/// and from a `u8` to a `char`, between any pointer and `void*` and from an
+/// enum whose variants carry nothing to an integer.
+fn can_cast(env: &Environment, from: &DataType, to: &DataType) -> bool {
+    let is_number = |datatype: &DataType| datatype.is_integer() || datatype.is_float();
+    let void_pointer = DataType::Pointer(Box::new(DataType::Void));
+
+    match (from, to) {
+        _ if from == to => true,
+        (from, to) if is_number(from) && is_number(to) => true,
+        (DataType::Char, to) => to.is_integer(),
+        (DataType::U8, DataType::Char) => true,
+        (DataType::Pointer(_), DataType::Pointer(_)) => {
+            *from == void_pointer || *to == void_pointer
+        }
+        (DataType::Compound { name }, to) if to.is_integer() => match env.get(name.lexeme.as_str())
+        {
+            Some(Value::Enum { ast }) => ast
+                .variants
+                .iter()
+                .all(|variant| variant.datatype.is_none()),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Types both operands of a binary operation, an unsuffixed number literal on
+/// either side takes the type of the other operand.
+fn evaluate_operands(
+    env: &Environment,
+    left: &expressions::Expression,
+    right: &expressions::Expression,
+) -> Result<(DataType, DataType), TypeError> {
+    if is_unsuffixed_number(left) && !is_unsuffixed_number(right) {
+        let right_type: DataType = evaluate_expr(env, right, None)?;
+        Ok((evaluate_expr(env, left, Some(&right_type))?, right_type))
+    } else {
+        let left_type: DataType = evaluate_expr(env, left, None)?;
+        let right_type: DataType = evaluate_expr(env, right, Some(&left_type))?;
+        Ok((left_type, right_type))
+    }
+}
+
+/// Whether `expr` is an integer or float literal without a suffix, negated or
+/// in parentheses, whose type depends on what's expected of it.
+fn is_unsuffixed_number(expr: &expressions::Expression) -> bool {
+    match expr {
+        expressions::Expression::Literal { literal, .. } => {
+            literal.suffix.is_none()
+                && matches!(
+                    literal.ttype,
+                    tokens::token_type::TokenType::Integer | tokens::token_type::TokenType::Double
+                )
+        }
+        expressions::Expression::Unary {
+            operation, value, ..
+        } => operation.ttype == tokens::token_type::TokenType::Minus && is_unsuffixed_number(value),
+        expressions::Expression::Nested { nested, .. } => is_unsuffixed_number(nested),
+        _ => false,
+    }
+}
+
+/// An index or a slice bound can be any integer, unsuffixed literals are taken as `usize`.
+/// `span` is the one of the brackets around it.
+fn evaluate_index(
+    env: &Environment,
+    index: &expressions::Expression,
+    span: Span,
+) -> Result<(), TypeError> {
+    let index_type: DataType = evaluate_expr(env, index, Some(&DataType::Usize))?;
+
+    if index_type.is_integer() {
+        Ok(())
+    } else {
+        Err(TypeError::InvalidIndex {
+            span,
+            found: index_type,
+        })
+    }
+}
+
+/// Type of the arithmetic, bitwise and logical binary operations. Both
+/// operands must have the same type, except for shifts where the amount can be
+/// any integer. An operand the operator doesn't accept is reported at `span`.
+fn evaluate_binary_operation(
+    (operator, span): (tokens::token_type::TokenType, Span),
+    left_type: DataType,
+    (right, right_type): (&expressions::Expression, DataType),
+) -> Result<DataType, TypeError> {
+    let accepts = |datatype: &DataType| -> bool {
+        match operator {
+            tokens::token_type::TokenType::And | tokens::token_type::TokenType::Or => {
+                *datatype == DataType::Bool
+            }
+            tokens::token_type::TokenType::Plus
+            | tokens::token_type::TokenType::Minus
+            | tokens::token_type::TokenType::Star
+            | tokens::token_type::TokenType::Slash
+            | tokens::token_type::TokenType::IntegerSlash
+            | tokens::token_type::TokenType::Mod
+            | tokens::token_type::TokenType::Power => datatype.is_integer() || datatype.is_float(),
+            tokens::token_type::TokenType::BitAnd
+            | tokens::token_type::TokenType::BitOr
+            | tokens::token_type::TokenType::BitXor
+            | tokens::token_type::TokenType::ShiftLeft
+            | tokens::token_type::TokenType::ShiftRight => datatype.is_integer(),
+            _ => panic!("somehow `{operator}` is used as a binary operator"),
+        }
+    };
+
+    for operand_type in [&left_type, &right_type] {
+        if !accepts(operand_type) {
+            return Err(TypeError::InvalidOperand {
+                span,
+                operator,
+                found: operand_type.clone(),
+            });
+        }
+    }
+
+    let is_shift = matches!(
+        operator,
+        tokens::token_type::TokenType::ShiftLeft | tokens::token_type::TokenType::ShiftRight
+    );
+    if !is_shift && left_type != right_type {
+        return Err(TypeError::UnexpectedType {
+            line: right.line(),
+            column: right.column(),
+            expected: left_type,
+            got: right_type,
+        });
+    }
+
+    Ok(left_type)
+}
+
+/// Types an integer literal, `negated` when it's the operand of a unary minus.
+fn evaluate_integer_literal(
+    literal: &Token,
+    expected: Option<&DataType>,
+    negated: bool,
+) -> Result<DataType, TypeError> {
+    let datatype = match (&literal.suffix, expected) {
+        (Some(suffix), _) => DataType::from_primitive(suffix).unwrap(),
+        (None, Some(expected)) if expected.is_integer() || expected.is_float() => expected.clone(),
+        (None, _) => DataType::I32,
+    };
+
+    match literal.lexeme.parse::<u64>() {
+        Ok(value) if literal_fits(value, &datatype, negated) => Ok(datatype),
+        _ => Err(TypeError::LiteralOutOfRange {
+            span: literal.span,
+            literal: match negated {
+                true => format!("-{}", literal.lexeme),
+                false => literal.lexeme.to_string(),
+            },
+            datatype,
+        }),
+    }
+}
+
+/// Checks whether an integer literal fits in `datatype`, a `negated` one may go
+/// down to the minimum of a signed type.
+fn literal_fits(value: u64, datatype: &DataType, negated: bool) -> bool {
+    datatype
+        .integer_range()
+        .is_none_or(|(min, max)| match negated {
+            // negating an unsigned value is refused by the operator itself
+            true => min == 0 || min <= -i128::from(value),
+            false => i128::from(value) <= max,
+        })
+}
@@ -1,12 +1,13 @@
 use clap::Parser;
-use cli_args::CliArgs;
+use cli_args::{CliArgs, Command};
 
 mod cli_args;
 
 fn main() {
     let args = CliArgs::parse();
-    match args.source {
-        Some(source) => ariel::compile(&source),
-        None => eprintln!("You need to provide the path to the source file to compile!"),
+    match (args.command, args.source) {
+        (Some(Command::Tokens { source, format }), _) => ariel::tokens(&source, format),
+        (None, Some(source)) => ariel::compile(&source),
+        (None, None) => eprintln!("You need to provide the path to the source file to compile!"),
     }
 }
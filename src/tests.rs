@@ -0,0 +1,533 @@
+use super::*;
+
+/// Type checks the body of the first function in `content`, with the enums
+/// and structs of `content` and the arguments of the function in scope.
+fn check(content: &str) -> Result<(), Vec<TypeError>> {
+    let mut source = SourceFile::from_string("check.test", content.to_owned());
+    let ast: ASTs = parser::parse_source(&mut source, Arc::new(Mutex::new(SourceMap::new())));
+
+    let mut env: Environment = HashMap::new();
+    env.extend(ast.enums.iter().map(|enumeration| {
+        (
+            enumeration.name.lexeme.as_str(),
+            Value::Enum { ast: enumeration },
+        )
+    }));
+    env.extend(
+        ast.structs
+            .iter()
+            .map(|class| (class.name.lexeme.as_str(), Value::Struct { ast: class })),
+    );
+
+    let func: &Function = &ast.fns[0];
+    env.extend(func.args.iter().map(|(arg, datatype)| {
+        (
+            arg.lexeme.as_str(),
+            Value::Variable {
+                datatype: datatype.clone(),
+            },
+        )
+    }));
+
+    validate_local_scope(&env, func.body.as_ref().unwrap(), &func.ret_type)
+}
+
+/// The single error `content` is expected to have.
+fn error_of(content: &str) -> TypeError {
+    match check(content) {
+        Err(mut errors) if errors.len() == 1 => errors.remove(0),
+        res => panic!("expected a single error in `{content}`, found {res:?}"),
+    }
+}
+
+#[test]
+fn signed_literal_bounds() {
+    for content in [
+        "fn f() -> i8 { return 127i8; }",
+        "fn f() -> i8 { return -128i8; }",
+        "fn f() -> i8 { return 127; }",
+        "fn f() -> i8 { return -128; }",
+        "fn f() -> i64 { return -9223372036854775808; }",
+    ] {
+        assert!(check(content).is_ok(), "`{content}` should type check");
+    }
+
+    for (content, out_of_range) in [
+        ("fn f() -> i8 { return 128i8; }", "128"),
+        ("fn f() -> i8 { return 128; }", "128"),
+        ("fn f() -> i8 { return -129; }", "-129"),
+        (
+            "fn f() -> i64 { return 9223372036854775808; }",
+            "9223372036854775808",
+        ),
+    ] {
+        match error_of(content) {
+            TypeError::LiteralOutOfRange { literal, .. } => {
+                assert_eq!(out_of_range, literal, "{content}")
+            }
+            e => panic!("expected `{out_of_range}` to be out of range, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn negating_unsigned_values() {
+    assert!(check("fn f(x: i32) -> f32 { return -1.5; }").is_ok());
+
+    for content in [
+        "fn f() -> u8 { return -1u8; }",
+        "fn f() -> u8 { return -0u8; }",
+        "fn f(x: u32) -> u32 { return -x; }",
+        "fn f(x: usize) -> usize { return -x; }",
+    ] {
+        match error_of(content) {
+            TypeError::InvalidOperand {
+                operator: tokens::token_type::TokenType::Minus,
+                ..
+            } => {}
+            e => panic!("expected `-` to be refused in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn cast_conversions() {
+    for content in [
+        "fn f() -> char { return 65 as char; }",
+        "fn f() -> u32 { return 'a' as u32; }",
+        "fn f() -> u32 { return -1 as u32; }",
+        "fn f(x: f64) -> i16 { return x as i16; }",
+        "fn f(x: char) -> u64 { return x as u64; }",
+        "fn f(p: i32*) -> void* { return p as void*; }",
+        "fn f(p: void*) -> u8* { return p as u8*; }",
+        "enum Color: u8 { Red, Green }\nfn f(c: Color) -> u8 { return c as u8; }",
+    ] {
+        assert!(check(content).is_ok(), "`{content}` should type check");
+    }
+
+    for content in [
+        "fn f(x: i32) -> char { return x as char; }",
+        "fn f(x: bool) -> i32 { return x as i32; }",
+        "fn f(p: i32*) -> u8* { return p as u8*; }",
+        "enum Shape { Circle(f32), Dot }\nfn f(s: Shape) -> u8 { return s as u8; }",
+    ] {
+        match error_of(content) {
+            TypeError::InvalidTypeConversion { .. } => {}
+            e => panic!("expected an invalid conversion in `{content}`, found {e:?}"),
+        }
+    }
+
+    assert!(matches!(
+        error_of("fn f() -> char { return 300 as char; }"),
+        TypeError::LiteralOutOfRange { .. }
+    ));
+}
+
+#[test]
+fn char_literals_are_not_integers() {
+    assert!(check("fn f() -> char { return 'a'; }").is_ok());
+    assert!(check("fn f() -> u32 { return 'a' as u32; }").is_ok());
+
+    for content in [
+        "fn f() -> u32 { return 'a'; }",
+        "fn f() -> u8 { return 'a'; }",
+    ] {
+        match error_of(content) {
+            TypeError::InvalidTypeConversion { from, .. } => assert_eq!(DataType::Char, from),
+            e => panic!("expected `'a'` to stay a `char` in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn integer_literals_are_not_chars() {
+    for content in [
+        "fn f() -> char { return 65; }",
+        "fn f() -> char { return 65u8; }",
+    ] {
+        match error_of(content) {
+            TypeError::InvalidTypeConversion { to, .. } => assert_eq!(DataType::Char, to),
+            e => panic!("expected `65` not to be a `char` in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn literals_adapt_to_the_other_operand() {
+    for content in [
+        "fn f(a: u8) -> bool { return a == 0; }",
+        "fn f(a: u8) -> bool { return 0 == a; }",
+        "fn f(a: u8) -> bool { return 200 < a; }",
+        "fn f(a: i8) -> bool { return -1 != a; }",
+        "fn f(a: f64) -> bool { return 1.5 >= a; }",
+        "fn f(a: u64) -> u64 { return 1 + a; }",
+        "fn f() -> bool { return 1 == 1; }",
+    ] {
+        assert!(check(content).is_ok(), "`{content}` should type check");
+    }
+
+    match error_of("fn f(a: u8) -> bool { return 300 == a; }") {
+        TypeError::LiteralOutOfRange { datatype, .. } => assert_eq!(DataType::U8, datatype),
+        e => panic!("expected `300` not to fit in a `u8`, found {e:?}"),
+    }
+}
+
+#[test]
+fn parenthesised_cast() {
+    assert!(check("fn f(a: i32) -> u8 { return (a as u8) * 2; }").is_ok());
+    assert!(check("fn f(a: i32) -> u8 { return (a as u8) * (2); }").is_ok());
+
+    match error_of("fn f(a: i32) -> i32 { return (a as u8) * 2; }") {
+        TypeError::InvalidTypeConversion { from, to, .. } => {
+            assert_eq!((DataType::U8, DataType::I32), (from, to))
+        }
+        e => panic!("expected the product to be a `u8`, found {e:?}"),
+    }
+}
+
+#[test]
+fn invalid_operands_point_at_the_operator() {
+    for (content, operator) in [
+        ("fn f(x: bool) -> i32 { return x + 1; }", 32),
+        ("fn f(x: u8) -> u8 { return -x; }", 27),
+    ] {
+        match error_of(content) {
+            TypeError::InvalidOperand { span, .. } => {
+                assert_eq!(operator, span.start, "{content}")
+            }
+            e => panic!("expected an invalid operand in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn indexing_errors_point_at_the_brackets() {
+    match error_of("fn f(x: bool) -> bool { return x[0]; }") {
+        TypeError::NotIndexable { span, .. } => assert_eq!((32, 35), (span.start, span.end)),
+        e => panic!("expected a value that can't be indexed, found {e:?}"),
+    }
+
+    match error_of("fn f(x: [i32]) -> [i32] { return x[1.5..]; }") {
+        TypeError::InvalidIndex { span, .. } => assert_eq!((34, 41), (span.start, span.end)),
+        e => panic!("expected an invalid index, found {e:?}"),
+    }
+}
+
+#[test]
+fn inconsistent_bindings_point_at_the_or_pattern() {
+    match error_of("fn f(n: i32) -> i32 { match n { x | 1 -> { return 1; } } return 0; }") {
+        TypeError::InconsistentBindings { span } => assert_eq!((32, 37), (span.start, span.end)),
+        e => panic!("expected inconsistent bindings, found {e:?}"),
+    }
+}
+
+/// The errors found in the definition of the first enum in `content`.
+fn enum_errors(content: &str) -> Vec<TypeError> {
+    let mut source = SourceFile::from_string("check.test", content.to_owned());
+    let ast: ASTs = parser::parse_source(&mut source, Arc::new(Mutex::new(SourceMap::new())));
+
+    evaluate_enum(&ast.enums[0])
+}
+
+#[test]
+fn valid_discriminants() {
+    for content in [
+        "enum E { A, B, C }",
+        "enum E: u8 { A = 3u8, B, C = 255 }",
+        "enum E: i8 { A = -128i8, B = 127 }",
+        "enum E: u64 { A = 18446744073709551615u64 }",
+        "enum E { A = 2, B = 0, C }",
+    ] {
+        let errors = enum_errors(content);
+        assert!(errors.is_empty(), "`{content}` should be valid: {errors:?}");
+    }
+}
+
+#[test]
+fn discriminant_suffixes() {
+    for (content, found, expected) in [
+        ("enum E: u8 { A = 3u16 }", DataType::U16, DataType::U8),
+        ("enum E { A = 1u8 }", DataType::U8, DataType::I32),
+        ("enum E: i16 { A, B = -2i8 }", DataType::I8, DataType::I16),
+    ] {
+        match &enum_errors(content)[..] {
+            [TypeError::DiscriminantSuffix {
+                suffix, backing, ..
+            }] => assert_eq!((&found, &expected), (suffix, backing), "{content}"),
+            errors => panic!("expected a mismatched suffix in `{content}`, found {errors:?}"),
+        }
+    }
+}
+
+#[test]
+fn discriminant_overflow() {
+    for (content, out_of_range) in [
+        ("enum E: u8 { A = 256 }", 256),
+        ("enum E: u8 { A = 255, B }", 256),
+        ("enum E: i8 { A = -129 }", -129),
+        ("enum E: u16 { A = -1 }", -1),
+    ] {
+        match &enum_errors(content)[..] {
+            [TypeError::DiscriminantOutOfRange { value, .. }] => {
+                assert_eq!(out_of_range, *value, "{content}")
+            }
+            errors => {
+                panic!("expected `{out_of_range}` not to fit in `{content}`, found {errors:?}")
+            }
+        }
+    }
+}
+
+#[test]
+fn duplicate_discriminants() {
+    match &enum_errors("enum E { A = 1, B = 0, C }")[..] {
+        [TypeError::DuplicateDiscriminant {
+            variant,
+            value,
+            taken_by,
+            ..
+        }] => assert_eq!(("C", 1, "A"), (variant.as_str(), *value, taken_by.as_str())),
+        errors => panic!("expected `C` to take the value of `A`, found {errors:?}"),
+    }
+}
+
+#[test]
+fn duplicate_variant_names() {
+    match &enum_errors("enum E { A, B, A }")[..] {
+        [TypeError::DuplicateVariant {
+            span,
+            first,
+            variant,
+            ..
+        }] => {
+            assert_eq!("A", variant);
+            assert_eq!((15, 9), (span.start, first.start));
+        }
+        errors => panic!("expected `A` to be defined twice, found {errors:?}"),
+    }
+}
+
+#[test]
+fn invalid_enum_definitions() {
+    assert!(matches!(
+        &enum_errors("enum E: f32 { A }")[..],
+        [TypeError::InvalidBacking {
+            backing: DataType::F32,
+            ..
+        }]
+    ));
+    assert!(matches!(
+        &enum_errors("enum E { A = x }")[..],
+        [TypeError::InvalidDiscriminant { .. }]
+    ));
+}
+
+const POINT: &str = "struct Point { x: i32, y: i32, }\n";
+
+#[test]
+fn struct_literal_fields() {
+    assert!(check(&format!(
+        "{POINT}fn f(a: i32) -> Point {{ return Point {{ y: a, x: 1 }}; }}"
+    ))
+    .is_ok());
+
+    match error_of(&format!(
+        "{POINT}fn f() -> Point {{ return Point {{ x: 1 }}; }}"
+    )) {
+        TypeError::MissingFields { fields, .. } => assert_eq!(vec!["y"], fields),
+        e => panic!("expected `y` to be missing, found {e:?}"),
+    }
+
+    match error_of(&format!(
+        "{POINT}fn f() -> Point {{ return Point {{ x: 1, y: 2, x: 3 }}; }}"
+    )) {
+        TypeError::DuplicateField { field, .. } => assert_eq!("x", field),
+        e => panic!("expected `x` to be given twice, found {e:?}"),
+    }
+
+    match error_of(&format!(
+        "{POINT}fn f() -> Point {{ return Point {{ x: 1, y: 2, z: 3 }}; }}"
+    )) {
+        TypeError::UnknownField { field, .. } => assert_eq!("z", field),
+        e => panic!("expected `z` to be unknown, found {e:?}"),
+    }
+
+    match error_of(&format!(
+        "{POINT}fn f() -> Point {{ return Point {{ x: 1, y: true }}; }}"
+    )) {
+        TypeError::UnexpectedType { expected, got, .. } => {
+            assert_eq!((DataType::I32, DataType::Bool), (expected, got))
+        }
+        e => panic!("expected `y` to be given a `bool`, found {e:?}"),
+    }
+}
+
+#[test]
+fn every_field_error_is_reported() {
+    let errors = check(&format!(
+        "{POINT}fn f() -> Point {{ return Point {{ x: true, z: 1, x: 2 }}; }}"
+    ))
+    .unwrap_err();
+
+    assert!(
+        matches!(
+            &errors[..],
+            [
+                TypeError::UnexpectedType { .. },
+                TypeError::UnknownField { .. },
+                TypeError::DuplicateField { .. },
+                TypeError::MissingFields { .. },
+            ]
+        ),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn range_pattern_bounds() {
+    for content in [
+        "fn f(n: u8) -> i32 { match n { 0..=255 -> { return 1; } } return 0; }",
+        "fn f(n: u8) -> i32 { match n { 0..0 -> { return 1; } } return 0; }",
+        "fn f(n: i8) -> i32 { match n { 0..128 -> { return 1; } } return 0; }",
+    ] {
+        assert!(check(content).is_ok(), "`{content}` should type check");
+    }
+
+    for (content, range, out_of_range, datatype) in [
+        (
+            "fn f(n: u8) -> i32 { match n { 0..=300 -> { return 1; } } return 0; }",
+            "0..=300",
+            "300",
+            DataType::U8,
+        ),
+        (
+            "fn f(n: i8) -> i32 { match n { 200..=210 -> { return 1; } } return 0; }",
+            "200..=210",
+            "200",
+            DataType::I8,
+        ),
+    ] {
+        match error_of(content) {
+            TypeError::LiteralOutOfRange {
+                span,
+                literal,
+                datatype: found,
+            } => {
+                assert_eq!(range, &content[span.start..span.end]);
+                assert_eq!((out_of_range, datatype), (literal.as_str(), found));
+            }
+            e => panic!("expected `{out_of_range}` not to fit in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn range_patterns_on_other_types() {
+    for (content, got) in [
+        (
+            "fn f(b: bool) -> i32 { match b { 0..=5 -> { return 1; } } return 0; }",
+            DataType::I32,
+        ),
+        (
+            "fn f(b: bool) -> i32 { match b { 0..=3000000000 -> { return 1; } } return 0; }",
+            DataType::I64,
+        ),
+    ] {
+        match error_of(content) {
+            TypeError::UnexpectedType {
+                expected,
+                got: found,
+                ..
+            } => assert_eq!((DataType::Bool, got), (expected, found), "{content}"),
+            e => panic!("expected a range to be refused in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn guards_must_be_bool() {
+    assert!(
+        check("fn f(n: i32) -> i32 { match n { x if x > 0 -> { return 1; } } return 0; }").is_ok()
+    );
+
+    match error_of("fn f(n: i32) -> i32 { match n { x if x -> { return 1; } } return 0; }") {
+        TypeError::UnexpectedType { expected, got, .. } => {
+            assert_eq!((DataType::Bool, DataType::I32), (expected, got))
+        }
+        e => panic!("expected the guard to be refused, found {e:?}"),
+    }
+}
+
+const SHAPE: &str = "enum Shape { Circle(f32), Dot }\n";
+
+#[test]
+fn payload_bindings() {
+    assert!(check(&format!(
+        "{SHAPE}fn f(s: Shape) -> f32 {{ match s {{ Shape::Circle(r) -> {{ return r; }} }} return 0.0; }}"
+    ))
+    .is_ok());
+
+    match error_of(&format!(
+        "{SHAPE}fn f(s: Shape) -> i32 {{ match s {{ Shape::Circle(r) -> {{ return r; }} }} return 0; }}"
+    )) {
+        TypeError::InvalidTypeConversion { from, to, .. } => {
+            assert_eq!((DataType::F32, DataType::I32), (from, to))
+        }
+        e => panic!("expected `r` to be a `f32`, found {e:?}"),
+    }
+}
+
+#[test]
+fn payload_types() {
+    assert!(check(&format!(
+        "{SHAPE}fn f() -> Shape {{ return Shape::Circle(1.5); }}"
+    ))
+    .is_ok());
+
+    for content in [
+        format!("{SHAPE}fn f() -> Shape {{ return Shape::Circle(true); }}"),
+        format!(
+            "{SHAPE}fn f(s: Shape) -> i32 {{ match s {{ Shape::Circle(true) -> {{ return 1; }} }} return 0; }}"
+        ),
+    ] {
+        match error_of(&content) {
+            TypeError::UnexpectedType { expected, got, .. } => {
+                assert_eq!((DataType::F32, DataType::Bool), (expected, got))
+            }
+            e => panic!("expected the payload to be refused in `{content}`, found {e:?}"),
+        }
+    }
+}
+
+#[test]
+fn interpolated_values() {
+    for datatype in [
+        "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize", "f32", "f64",
+        "bool", "char", "str",
+    ] {
+        let content = format!("fn f(x: {datatype}) -> str {{ return \"x is {{x}}\"; }}");
+        assert!(check(&content).is_ok(), "`{content}` should type check");
+    }
+
+    for (content, found) in [
+        (
+            format!("{POINT}fn f(p: Point) -> str {{ return \"at {{p}}\"; }}"),
+            "Point",
+        ),
+        (
+            "fn f(p: i32*) -> str { return \"at {p}\"; }".to_owned(),
+            "i32*",
+        ),
+        (
+            "fn f(a: [u8]) -> str { return \"all of {a}\"; }".to_owned(),
+            "[u8]",
+        ),
+    ] {
+        match error_of(&content) {
+            TypeError::NotFormattable {
+                found: datatype, ..
+            } => assert_eq!(found, datatype.to_string(), "{content}"),
+            e => panic!("expected `{found}` not to be formattable, found {e:?}"),
+        }
+    }
+}
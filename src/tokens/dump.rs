@@ -0,0 +1,166 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+
+use super::{source::SourceFile, token::Token, token_type::TokenType, tokenizer};
+
+/// How `dump` prints the token stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One aligned row per token, meant to be read by people.
+    #[default]
+    Table,
+    /// One JSON object per line, meant to be read by editors and scripts.
+    Json,
+}
+
+/// Lexes `source` up to its end with `tokenizer::get_token` and writes every
+/// token to `out`, `Eof` excluded.
+///
+/// Lines are 1-based, columns are 0-based and counted in characters, spans are
+/// byte offsets into the file. The table shows the text the token spans as it
+/// is written in the file, e.g. `0xFF`, the JSON `lexeme` is the normalized
+/// one, e.g. `255`.
+pub fn dump(source: &mut SourceFile, format: Format, out: &mut impl Write) -> io::Result<()> {
+    if format == Format::Table {
+        writeln!(
+            out,
+            "{:<10} {:<12} {:<20} LEXEME",
+            "LOCATION", "SPAN", "TYPE"
+        )?;
+    }
+
+    loop {
+        let token = tokenizer::get_token(source);
+        if token.ttype == TokenType::Eof {
+            return Ok(());
+        }
+
+        let (line, column) = source.line_column(token.span.start);
+        match format {
+            Format::Table => writeln!(
+                out,
+                "{:<10} {:<12} {:<20} {:?}{}",
+                format!("{line}:{column}"),
+                format!("{}..{}", token.span.start, token.span.end),
+                type_name(&token),
+                source.lexeme_between(token.span.start, token.span.end),
+                match &token.ttype {
+                    TokenType::Error(e) => format!(" ({e})"),
+                    _ => String::new(),
+                }
+            )?,
+            Format::Json => {
+                write!(
+                    out,
+                    "{{\"type\":{},\"lexeme\":{},\"file\":{},\"line\":{line},\"column\":{column},\"span\":{{\"start\":{},\"end\":{}}}",
+                    json_string(&type_name(&token)),
+                    json_string(&token.lexeme),
+                    json_string(source.name()),
+                    token.span.start,
+                    token.span.end
+                )?;
+                if let TokenType::Error(e) = &token.ttype {
+                    write!(out, ",\"error\":{}", json_string(&e.to_string()))?;
+                }
+                writeln!(out, "}}")?;
+            }
+        }
+    }
+}
+
+/// The variant name of the token type, followed by the suffix of number
+/// literals, e.g. `Integer:U8`.
+fn type_name(token: &Token) -> String {
+    let name = |ttype: &TokenType| match ttype {
+        TokenType::Error(_) => "Error".to_owned(),
+        ttype => format!("{ttype:?}"),
+    };
+
+    match &token.suffix {
+        Some(suffix) => format!("{}:{}", name(&token.ttype), name(suffix)),
+        None => name(&token.ttype),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump_to_string(content: &str, format: Format) -> String {
+        let mut source = SourceFile::from_string("dump.test", content.to_owned());
+        let mut out = Vec::new();
+        dump(&mut source, format, &mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn table_dump() {
+        let table = dump_to_string("let x := 1;\n  x += 2;", Format::Table);
+        let rows: Vec<&str> = table.lines().collect();
+
+        assert_eq!(10, rows.len());
+        assert!(rows[0].starts_with("LOCATION"));
+        assert_eq!(
+            "1:4        4..5         Identifier           \"x\"",
+            rows[2]
+        );
+        assert_eq!(
+            "2:4        16..18       PlusEquals           \"+=\"",
+            rows[7]
+        );
+    }
+
+    #[test]
+    fn table_shows_the_source_text() {
+        let table = dump_to_string("0xFF 1_000u16 'a'", Format::Table);
+        let lexemes: Vec<&str> = table.lines().skip(1).map(|row| &row[45..]).collect();
+
+        assert_eq!(vec![r#""0xFF""#, r#""1_000u16""#, r#""'a'""#], lexemes);
+    }
+
+    #[test]
+    fn json_dump() {
+        let json = dump_to_string("s := \"a\\\"b\";", Format::Json);
+        let records: Vec<&str> = json.lines().collect();
+
+        assert_eq!(4, records.len());
+        assert_eq!(
+            r#"{"type":"Identifier","lexeme":"s","file":"dump.test","line":1,"column":0,"span":{"start":0,"end":1}}"#,
+            records[0]
+        );
+        assert_eq!(
+            r#"{"type":"String","lexeme":"a\"b","file":"dump.test","line":1,"column":5,"span":{"start":5,"end":11}}"#,
+            records[2]
+        );
+    }
+
+    #[test]
+    fn json_dump_reports_lexical_errors() {
+        let json = dump_to_string("a $", Format::Json);
+
+        assert!(json.lines().nth(1).is_some_and(|record| record
+            .starts_with(r#"{"type":"Error","lexeme":"$""#)
+            && record.contains(r#","error":"#)));
+    }
+}
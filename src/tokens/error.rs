@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use crate::ast_generator::ast::expressions::Expression;
 
 use super::{token::Token, token_type::TokenType};
@@ -6,6 +8,7 @@ use super::{token::Token, token_type::TokenType};
 pub enum Error<'a> {
     FileNotFound(&'a str, String),
     MemoryMapFiled(&'a str, String),
+    StdinUnreadable(String),
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -48,4 +51,97 @@ pub enum ParseError {
     InvalidAddressOfValue {
         at: Box<Token>,
     },
+    /// `arm` starts a match arm matching exactly what the arm at `first` does.
+    DuplicateMatchArm {
+        arm: Box<Token>,
+        first: Box<Token>,
+    },
+    /// `arm` starts a match arm that can never be reached because the arm at
+    /// `covered_by` already matches everything it does.
+    UnreachableMatchArm {
+        arm: Box<Token>,
+        covered_by: Box<Token>,
+    },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub enum LexError {
+    MissingDigits { radix: u32 },
+    InvalidDigit { radix: u32, digit: char },
+    MissingExponent,
+    InvalidSuffix { suffix: String },
+    IntegerTooLarge,
+    EmptyCharLiteral,
+    OverlongCharLiteral,
+    UnterminatedCharLiteral,
+    InvalidEscape { escape: char },
+    InvalidUnicodeEscape,
+    InvalidHexEscape,
+    InvalidUtf8,
+    UnterminatedString,
+    UnterminatedBlockComment { closing: &'static str },
+    UnexpectedCharacter { ch: char },
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexError::MissingDigits { radix } => write!(
+                f,
+                "expected at least one {} digit after the `{}` prefix",
+                radix_name(*radix),
+                radix_prefix(*radix)
+            ),
+            LexError::InvalidDigit { radix, digit } => {
+                write!(f, "`{digit}` is not a valid {} digit", radix_name(*radix))
+            }
+            LexError::MissingExponent => write!(f, "expected at least one digit in the exponent"),
+            LexError::InvalidSuffix { suffix } => {
+                write!(f, "`{suffix}` is not a valid suffix for a number literal")
+            }
+            LexError::IntegerTooLarge => write!(f, "integer literal doesn't fit in 64 bits"),
+            LexError::EmptyCharLiteral => write!(f, "a character literal can't be empty"),
+            LexError::OverlongCharLiteral => {
+                write!(f, "a character literal must contain exactly one character")
+            }
+            LexError::UnterminatedCharLiteral => write!(f, "unterminated character literal"),
+            LexError::InvalidEscape { escape } => {
+                write!(f, "`\\{escape}` is not a valid escape sequence")
+            }
+            LexError::InvalidUnicodeEscape => write!(
+                f,
+                "a unicode escape must look like `\\u{{...}}` with 1 to 6 hexadecimal digits naming a valid code point"
+            ),
+            LexError::InvalidHexEscape => write!(
+                f,
+                "a hex escape must look like `\\x..` with exactly two hexadecimal digits up to `7F`"
+            ),
+            LexError::InvalidUtf8 => write!(f, "invalid UTF-8 byte sequence"),
+            LexError::UnterminatedString => write!(f, "unterminated string literal"),
+            LexError::UnterminatedBlockComment { closing } => {
+                write!(f, "unterminated block comment, it should be closed by a `{closing}`")
+            }
+            LexError::UnexpectedCharacter { ch } => {
+                write!(f, "`{ch}` isn't valid anywhere in a program")
+            }
+        }
+    }
+}
+
+fn radix_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    }
+}
+
+fn radix_prefix(radix: u32) -> &'static str {
+    match radix {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => "",
+    }
 }
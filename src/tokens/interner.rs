@@ -0,0 +1,119 @@
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    fmt::Display,
+    hash::{BuildHasherDefault, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+/// FNV-1a, lexemes are a handful of bytes long so it's much cheaper than the
+/// default SipHash and there's no untrusted input to defend against.
+pub struct LexemeHasher(u64);
+
+impl Default for LexemeHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for LexemeHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+pub type BuildLexemeHasher = BuildHasherDefault<LexemeHasher>;
+
+/// The text of a token, cloning it only bumps a reference count.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Lexeme(Arc<str>);
+
+impl Lexeme {
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Lexeme {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Lexeme {
+    #[inline]
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Lexeme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// For tokens made outside of a lexer, the text isn't shared with anything.
+impl From<&str> for Lexeme {
+    fn from(text: &str) -> Self {
+        Self(Arc::from(text))
+    }
+}
+
+impl PartialEq<str> for Lexeme {
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<&str> for Lexeme {
+    fn eq(&self, other: &&str) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<Lexeme> for str {
+    fn eq(&self, other: &Lexeme) -> bool {
+        *self == *other.0
+    }
+}
+
+impl PartialEq<Lexeme> for &str {
+    fn eq(&self, other: &Lexeme) -> bool {
+        **self == *other.0
+    }
+}
+
+/// The lexemes of one source file, every token with the same text shares a
+/// single copy of it. A text is freed once the file and every token holding it
+/// are dropped.
+#[derive(Default)]
+pub struct Interner {
+    lexemes: HashSet<Lexeme, BuildLexemeHasher>,
+}
+
+impl Interner {
+    /// Returns the copy of `text` shared by every token of the file, storing it
+    /// the first time it's seen.
+    pub fn intern(&mut self, text: &str) -> Lexeme {
+        match self.lexemes.get(text) {
+            Some(lexeme) => lexeme.clone(),
+            None => {
+                let lexeme = Lexeme::from(text);
+                self.lexemes.insert(lexeme.clone());
+                lexeme
+            }
+        }
+    }
+}
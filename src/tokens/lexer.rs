@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use super::{source::SourceFile, token::Token, token_type::TokenType, tokenizer};
+
+/// Streams the tokens of a `SourceFile`, `Eof` ends the iteration instead of
+/// being yielded.
+///
+/// Lexical errors are yielded as `TokenType::Error` tokens like any other, so
+/// a malformed file is still lexed up to its end.
+///
+/// ```
+/// use ariel::tokens::{lexer::Lexer, source::SourceFile, token_type::TokenType};
+///
+/// let source = SourceFile::from_string("example", "let x := 1;".to_owned());
+/// let mut lexer = Lexer::new(source);
+///
+/// assert_eq!(Some(TokenType::Identifier), lexer.peek_nth(1).map(|tk| tk.ttype.clone()));
+/// assert_eq!(5, lexer.count());
+/// ```
+pub struct Lexer {
+    source: SourceFile,
+
+    /// Tokens already lexed by `peek` and `peek_nth`, in source order.
+    lookahead: VecDeque<Token>,
+}
+
+impl Lexer {
+    pub fn new(source: SourceFile) -> Self {
+        Self {
+            source,
+            lookahead: VecDeque::new(),
+        }
+    }
+
+    /// The file being lexed, spans of the yielded tokens are resolved with it.
+    #[inline]
+    pub fn source(&self) -> &SourceFile {
+        &self.source
+    }
+
+    /// The token `next` is going to yield, without consuming it.
+    #[inline]
+    pub fn peek(&mut self) -> Option<&Token> {
+        self.peek_nth(0)
+    }
+
+    /// The token `n` positions after the one `next` is going to yield,
+    /// `peek_nth(0)` is the same as `peek`.
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        while self.lookahead.len() <= n {
+            match self.lookahead.back() {
+                Some(token) if token.ttype == TokenType::Eof => return None,
+                _ => self
+                    .lookahead
+                    .push_back(tokenizer::get_token(&mut self.source)),
+            }
+        }
+
+        self.lookahead
+            .get(n)
+            .filter(|token| token.ttype != TokenType::Eof)
+    }
+}
+
+impl Iterator for Lexer {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let token = match self.lookahead.pop_front() {
+            Some(token) => token,
+            None => tokenizer::get_token(&mut self.source),
+        };
+
+        match token.ttype {
+            TokenType::Eof => {
+                // keeps yielding `None` once the end has been reached
+                self.lookahead.push_front(token);
+                None
+            }
+            _ => Some(token),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::interner::Lexeme;
+
+    fn lexer(content: &str) -> Lexer {
+        Lexer::new(SourceFile::from_string("lexer.test", content.to_owned()))
+    }
+
+    #[test]
+    fn lexer_yields_tokens_up_to_eof() {
+        let lexemes: Vec<Lexeme> = lexer("fn main() {}").map(|tk| tk.lexeme).collect();
+
+        assert_eq!(vec!["fn", "main", "(", ")", "{", "}"], lexemes);
+    }
+
+    #[test]
+    fn lexer_stays_exhausted() {
+        let mut lexer = lexer("x");
+
+        assert_eq!(Some("x"), lexer.next().map(|tk| tk.lexeme).as_deref());
+        assert!(lexer.next().is_none());
+        assert!(lexer.next().is_none());
+        assert!(lexer.peek().is_none());
+    }
+
+    #[test]
+    fn lexer_peeks_without_consuming() {
+        let mut lexer = lexer("a b c");
+
+        assert_eq!(Some("c"), lexer.peek_nth(2).map(|tk| tk.lexeme.as_str()));
+        assert_eq!(Some("a"), lexer.peek().map(|tk| tk.lexeme.as_str()));
+        assert!(lexer.peek_nth(3).is_none());
+        assert!(lexer.peek_nth(10).is_none());
+
+        let lexemes: Vec<Lexeme> = lexer.map(|tk| tk.lexeme).collect();
+        assert_eq!(vec!["a", "b", "c"], lexemes);
+    }
+
+    #[test]
+    fn lexer_yields_lexical_errors() {
+        let mut lexer = lexer("a $ b");
+
+        assert_eq!(Some(TokenType::Identifier), lexer.next().map(|tk| tk.ttype));
+        assert!(matches!(
+            lexer.next().map(|tk| tk.ttype),
+            Some(TokenType::Error(_))
+        ));
+        assert_eq!(Some("b"), lexer.next().map(|tk| tk.lexeme).as_deref());
+    }
+}
@@ -1,6 +1,12 @@
 pub mod source;
+pub mod source_map;
 
+pub mod dump;
 pub mod error;
+pub mod interner;
+pub mod lexer;
+pub mod relex;
 pub mod token;
 pub mod token_type;
 pub mod tokenizer;
+pub mod trivia;
@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use super::{error::LexError, source::SourceFile, token::Token, token_type::TokenType, tokenizer};
+
+/// Replaces the bytes in `range` with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl Edit {
+    pub fn new(range: Range<usize>, text: &str) -> Self {
+        Self {
+            range,
+            text: text.to_owned(),
+        }
+    }
+
+    /// A copy of `source` with the edit applied, under the same name and file id.
+    pub fn apply(&self, source: &SourceFile) -> SourceFile {
+        let contents: &[u8] = source.contents();
+
+        let mut edited: Vec<u8> =
+            Vec::with_capacity(contents.len() - self.range.len() + self.text.len());
+        edited.extend_from_slice(&contents[..self.range.start]);
+        edited.extend_from_slice(self.text.as_bytes());
+        edited.extend_from_slice(&contents[self.range.end..]);
+
+        let mut edited = SourceFile::from_bytes(source.name(), &edited);
+        edited.id = source.id;
+
+        edited
+    }
+
+    /// Where a byte at or after the end of the edited range ends up.
+    #[inline]
+    fn shift(&self, offset: usize) -> usize {
+        offset - self.range.len() + self.text.len()
+    }
+}
+
+/// Brings `tokens`, the whole stream of a file up to and including `Eof`, up
+/// to date with `edit`. `source` is the file with the edit already applied.
+///
+/// Lexing restarts from the last boundary in front of the edit where the
+/// tokenizer holds no state and stops at the first such boundary of the old
+/// stream past the edit, the tokens after it are only moved. Returns where the
+/// relexed tokens are in the updated stream.
+pub fn relex(source: &mut SourceFile, tokens: &mut Vec<Token>, edit: &Edit) -> Range<usize> {
+    let resumable: Vec<bool> = resumable_boundaries(tokens);
+
+    // the token in front of the first one touching the edit may have looked
+    // into it, e.g. `r` followed by `#x` becoming `#"`
+    let first_touched: usize = tokens
+        .iter()
+        .position(|tk| tk.span.end >= edit.range.start)
+        .unwrap_or(tokens.len());
+    let kept: usize = (0..first_touched.saturating_sub(1))
+        .rev()
+        .find(|index| resumable[*index])
+        .map_or(0, |index| index + 1);
+
+    let restart: usize = match kept {
+        0 => 0,
+        kept => tokens[kept - 1].span.end,
+    };
+    source.start = restart;
+    source.current = restart;
+    source.pending.clear();
+    source.interpolations.clear();
+
+    let mut relexed: Vec<Token> = vec![];
+    let mut old: usize = kept;
+    loop {
+        let token = tokenizer::get_token(source);
+        let end: usize = token.span.end;
+        let is_eof: bool = token.ttype == TokenType::Eof;
+        let is_resumable: bool = !matches!(token.ttype, TokenType::Error(_))
+            && source.pending.is_empty()
+            && source.interpolations.is_empty();
+        relexed.push(token);
+
+        if is_eof {
+            old = tokens.len();
+            break;
+        }
+
+        if !is_resumable || end < edit.range.start + edit.text.len() {
+            continue;
+        }
+
+        // the old boundaries past the edit, moved to where they are now
+        while old < tokens.len()
+            && (!resumable[old]
+                || tokens[old].span.end < edit.range.end
+                || edit.shift(tokens[old].span.end) < end)
+        {
+            old += 1;
+        }
+
+        if old < tokens.len() && edit.shift(tokens[old].span.end) == end {
+            old += 1;
+            break;
+        }
+    }
+
+    for token in tokens[old..].iter_mut() {
+        token.span.start = edit.shift(token.span.start);
+        token.span.end = edit.shift(token.span.end);
+    }
+
+    let relexed_count: usize = relexed.len();
+    tokens.splice(kept..old, relexed);
+
+    kept..kept + relexed_count
+}
+
+/// Whether the tokenizer is left without state after each token: no error
+/// waiting in `pending` and no interpolated string still open.
+fn resumable_boundaries(tokens: &[Token]) -> Vec<bool> {
+    let mut open_strings: usize = 0;
+    // a string part with broken escapes is replaced by its errors, from there
+    // on the open strings can't be counted anymore
+    let mut countable: bool = true;
+
+    tokens
+        .iter()
+        .map(|tk| {
+            match &tk.ttype {
+                TokenType::StringHead => open_strings += 1,
+                TokenType::StringTail => match open_strings.checked_sub(1) {
+                    Some(open) => open_strings = open,
+                    None => countable = false,
+                },
+                TokenType::Error(
+                    LexError::InvalidEscape { .. }
+                    | LexError::InvalidUnicodeEscape
+                    | LexError::InvalidHexEscape
+                    | LexError::InvalidUtf8
+                    | LexError::UnterminatedString,
+                ) => countable = false,
+                _ => {}
+            }
+
+            countable && open_strings == 0 && !matches!(tk.ttype, TokenType::Error(_))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(source: &mut SourceFile) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        loop {
+            let token = tokenizer::get_token(source);
+            let is_eof: bool = token.ttype == TokenType::Eof;
+            tokens.push(token);
+
+            if is_eof {
+                return tokens;
+            }
+        }
+    }
+
+    /// Relexes `content` after replacing `range` with `text` and checks the
+    /// result against a full rescan of the edited file.
+    fn assert_relex(content: &str, range: Range<usize>, text: &str) -> Range<usize> {
+        let mut source = SourceFile::from_string("relex.test", content.to_owned());
+        let mut tokens: Vec<Token> = scan(&mut source);
+        let edit = Edit::new(range, text);
+
+        let relexed = relex(&mut edit.apply(&source), &mut tokens, &edit);
+        assert_eq!(
+            scan(&mut edit.apply(&source)),
+            tokens,
+            "{content:?} {edit:?}"
+        );
+
+        relexed
+    }
+
+    #[test]
+    fn edit_applies_to_source() {
+        let source = SourceFile::from_string("relex.test", "let x := 1;".to_owned());
+        let edited = Edit::new(4..5, "value").apply(&source);
+
+        assert_eq!(b"let value := 1;", edited.contents());
+        assert_eq!("relex.test", edited.name());
+    }
+
+    #[test]
+    fn relex_only_the_edited_region() {
+        let content = "let x := 1;\nlet y := 2;\nlet z := 3;";
+
+        // the token in front of the edit is always relexed
+        assert_eq!(5..7, assert_relex(content, 16..17, "why"));
+        assert_eq!(5..7, assert_relex(content, 16..17, ""));
+        assert_eq!(0..1, assert_relex(content, 0..0, " "));
+    }
+
+    #[test]
+    fn relex_matches_rescan() {
+        let content = "fn main() {\n    s := \"x = {a + b} y\";\n    n := 1..2;\n    r#x\n    v := c; # note\n}";
+        let len: usize = content.len();
+
+        let edits: [(Range<usize>, &str); 15] = [
+            // joining and splitting tokens
+            (2..3, ""),
+            (4..4, " "),
+            // opening and closing strings and comments
+            (16..16, "\""),
+            (35..36, ""),
+            (16..16, "# "),
+            (47..47, "##"),
+            // inside an interpolation hole
+            (29..30, "{"),
+            (27..27, "}"),
+            (65..65, "\"{"),
+            // a number becoming a float
+            (48..50, "."),
+            // `r#x` becoming a raw string
+            (59..60, "\"a\"#"),
+            (0..len, ""),
+            (len..len, "\n"),
+            (len - 1..len, ""),
+            (0..0, "#: doc\n"),
+        ];
+
+        for (range, text) in edits {
+            assert_relex(content, range, text);
+        }
+    }
+
+    #[test]
+    fn relex_after_broken_strings() {
+        let content = "a := \"\\q{b}\\q\";\nc := \"{d}\" + e;\nf := 1;";
+
+        assert_relex(content, 37..38, "g");
+        assert_relex(content, 16..17, "h");
+        assert_relex(content, 25..26, "");
+    }
+
+    #[test]
+    fn relex_at_every_offset() {
+        let content = "x := \"a{b}c\" ## block ## + 1.5; #[ n #[ e ]# ]# y";
+
+        for start in 0..content.len() {
+            for text in ["", "\"", "{", "}", "#", "a", " "] {
+                assert_relex(content, start..start, text);
+                assert_relex(content, start..start + 1, text);
+            }
+        }
+    }
+}
@@ -1,23 +1,103 @@
-use std::fs::File;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::VecDeque,
+    fs::File,
+    io::{self, Read},
+    ops::Deref,
+    sync::Arc,
+};
 
 use memmap2::Mmap;
 
-use super::error::Error;
+use super::{
+    error::Error,
+    interner::{Interner, Lexeme},
+    source_map::FileId,
+    token::Token,
+};
 
 #[allow(dead_code)]
 pub struct SourceFile {
-    pub name: String,
-    pub line: usize,
-    pub column: usize,
+    /// Set once the file is registered in a `SourceMap`.
+    pub id: FileId,
     size: usize,
 
     pub start: usize,
     pub current: usize,
-    mmap: Mmap,
+    text: Arc<SourceText>,
+
+    /// Tokens already lexed but not yet handed out, e.g. when a single
+    /// string literal contains more than one invalid escape.
+    pub pending: VecDeque<Token>,
+
+    /// Brace depth inside every interpolation hole still open, innermost last.
+    pub interpolations: Vec<usize>,
+
+    /// The lexemes of the tokens lexed so far, freed along with the file.
+    lexemes: RefCell<Interner>,
+}
+
+/// The bytes of a source file together with where each of its lines starts.
+pub struct SourceText {
+    pub name: String,
+    contents: Contents,
+    line_starts: Vec<usize>,
+}
+
+/// Where the bytes of a `SourceFile` live.
+enum Contents {
+    Mapped(Mmap),
+    InMemory(Vec<u8>),
+}
+
+impl Deref for Contents {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Contents::Mapped(mmap) => mmap,
+            Contents::InMemory(bytes) => bytes,
+        }
+    }
+}
+
+impl SourceText {
+    fn new(name: &str, contents: Contents) -> Self {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(
+                contents
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, byte)| **byte == b'\n')
+                    .map(|(index, _)| index + 1),
+            )
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            contents,
+            line_starts,
+        }
+    }
+
+    /// 1-based line and 0-based column of the byte at `offset`, columns are
+    /// counted in characters.
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let line: usize = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start: usize = self.line_starts[line - 1];
+
+        let column: usize = self.contents[line_start..offset.min(self.contents.len())]
+            .iter()
+            .filter(|byte| **byte & 0xC0 != 0x80)
+            .count();
+
+        (line, column)
+    }
 }
 
 impl SourceFile {
-    pub fn new(path: &str) -> Result<Self, Error> {
+    pub fn new(path: &str) -> Result<Self, Error<'_>> {
         let file = match File::open(path) {
             Ok(file) => file,
             Err(e) => return Err(Error::FileNotFound(path, e.to_string())),
@@ -30,20 +110,93 @@ impl SourceFile {
             }
         };
 
-        Ok(Self {
-            line: 1,
-            column: 0,
-            size: mmap.len(),
+        Ok(Self::with_contents(path, Contents::Mapped(mmap)))
+    }
+
+    /// `name` is only used to tell where tokens were found in.
+    pub fn from_string(name: &str, contents: String) -> Self {
+        Self::with_contents(name, Contents::InMemory(contents.into_bytes()))
+    }
+
+    /// `name` is only used to tell where tokens were found in.
+    pub fn from_bytes(name: &str, contents: &[u8]) -> Self {
+        Self::with_contents(name, Contents::InMemory(contents.to_vec()))
+    }
+
+    /// Reads the whole standard input, the source is named `<stdin>`.
+    pub fn from_stdin() -> Result<Self, Error<'static>> {
+        let mut contents: Vec<u8> = vec![];
+        if let Err(e) = io::stdin().read_to_end(&mut contents) {
+            return Err(Error::StdinUnreadable(e.to_string()));
+        }
+
+        Ok(Self::with_contents("<stdin>", Contents::InMemory(contents)))
+    }
+
+    fn with_contents(name: &str, contents: Contents) -> Self {
+        Self {
+            id: FileId::default(),
+            size: contents.len(),
             start: 0,
             current: 0,
-            name: path.to_string(),
-            mmap,
-        })
+            text: Arc::new(SourceText::new(name, contents)),
+            pending: VecDeque::new(),
+            interpolations: vec![],
+            lexemes: RefCell::default(),
+        }
+    }
+
+    /// Shares `text` with the other tokens of the file having the same lexeme.
+    #[inline]
+    pub fn intern(&self, text: &str) -> Lexeme {
+        self.lexemes.borrow_mut().intern(text)
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.text.name
+    }
+
+    #[inline]
+    pub fn text(&self) -> Arc<SourceText> {
+        Arc::clone(&self.text)
+    }
+
+    #[inline]
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        self.text.line_column(offset)
+    }
+
+    /// The raw bytes of the whole file.
+    #[inline]
+    pub fn contents(&self) -> &[u8] {
+        &self.text.contents
     }
 
     #[inline]
-    pub fn build_lexeme(&self) -> String {
-        String::from_utf8_lossy(&self.mmap[self.start..self.current]).to_string()
+    pub fn build_lexeme(&self) -> Cow<'_, str> {
+        self.lexeme_between(self.start, self.current)
+    }
+
+    #[inline]
+    pub fn lexeme_from(&self, start: usize) -> Cow<'_, str> {
+        self.lexeme_between(start, self.current)
+    }
+
+    /// Borrows the text straight from the file unless it isn't valid UTF-8.
+    #[inline]
+    pub fn lexeme_between(&self, start: usize, end: usize) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.text.contents[start..end])
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    pub fn is_at_end(&self) -> bool {
+        self.current >= self.size
     }
 
     #[inline]
@@ -56,9 +209,23 @@ impl SourceFile {
         self.peek_at(1)
     }
 
+    /// Decodes the UTF-8 character under the cursor without consuming it.
+    pub fn peek_char(&self) -> Option<char> {
+        let len: usize = match self.peek() {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return None,
+        };
+
+        let bytes = self.text.contents.get(self.current..self.current + len)?;
+        std::str::from_utf8(bytes).ok()?.chars().next()
+    }
+
     #[inline]
-    fn peek_at(&self, index: usize) -> u8 {
-        match self.mmap.get(self.current + index) {
+    pub fn peek_at(&self, index: usize) -> u8 {
+        match self.text.contents.get(self.current + index) {
             Some(value) => *value,
             None => 0,
         }
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use super::source::{SourceFile, SourceText};
+
+/// Compact handle to a file registered in a `SourceMap`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileId(u32);
+
+/// Byte range `start..end` of a file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub file: FileId,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file: FileId, start: usize, end: usize) -> Self {
+        Self { file, start, end }
+    }
+}
+
+/// Every file taking part in a compilation, spans are resolved to names,
+/// lines and columns through it.
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<Arc<SourceText>>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `source`, the tokens lexed from it from now on carry the
+    /// returned id.
+    pub fn add(&mut self, source: &mut SourceFile) -> FileId {
+        let id = FileId(self.files.len() as u32);
+
+        self.files.push(source.text());
+        source.id = id;
+        id
+    }
+
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files
+            .iter()
+            .position(|file| file.name == name)
+            .map(|index| FileId(index as u32))
+    }
+
+    pub fn name(&self, file: FileId) -> &str {
+        &self.files[file.0 as usize].name
+    }
+
+    /// 1-based line and 0-based column, in characters, where `span` starts.
+    pub fn line_column(&self, span: Span) -> (usize, usize) {
+        self.files[span.file.0 as usize].line_column(span.start)
+    }
+}
@@ -1,26 +1,15 @@
 use std::fmt::Display;
 
-use super::token_type::TokenType;
+use super::{interner::Lexeme, source_map::Span, token_type::TokenType};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub struct Token {
-    pub line: usize,
-    pub column: usize,
+    pub span: Span,
     pub ttype: TokenType,
-    pub lexeme: String,
-    pub found_in: String,
-}
-
-impl Default for Token {
-    fn default() -> Self {
-        Self {
-            line: Default::default(),
-            column: Default::default(),
-            ttype: Default::default(),
-            lexeme: Default::default(),
-            found_in: Default::default(),
-        }
-    }
+    /// Interned by the source file it comes from, so copying a token never
+    /// copies its text.
+    pub lexeme: Lexeme,
+    pub suffix: Option<TokenType>,
 }
 
 impl Display for Token {
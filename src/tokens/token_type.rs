@@ -2,10 +2,12 @@ use std::fmt::Display;
 
 use colored::Colorize;
 
+use super::error::LexError;
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TokenType {
     Eof,
-    Unknown(char),
+    Error(LexError),
 
     LeftParen,
     RightParen,
@@ -27,6 +29,7 @@ pub enum TokenType {
     Question,
 
     Mod,
+    ModEquals,
     Not,
     NotEqual,
     Equal,
@@ -41,8 +44,13 @@ pub enum TokenType {
     ShiftLeftEqual,
     And,
     BitAnd,
+    BitAndEquals,
     Or,
     BitOr,
+    BitOrEquals,
+    BitXor,
+    BitXorEquals,
+    BitNot,
     Match,
     Minus,
     MinusEquals,
@@ -60,10 +68,15 @@ pub enum TokenType {
     Double,
     Integer,
     String,
+    StringHead,
+    StringMiddle,
+    StringTail,
+    Char,
     True,
     False,
     Identifier,
     Nil,
+    DocComment,
 
     U8,
     U16,
@@ -76,10 +89,12 @@ pub enum TokenType {
     F32,
     F64,
     StringType,
+    CharType,
     Bool,
     Void,
 
     DontCare,
+    As,
     Break,
     Continue,
     Else,
@@ -94,15 +109,41 @@ pub enum TokenType {
     Return,
     Struct,
     While,
-    InvalidByteSequenceToString,
     Defer,
     Usize,
     Isize,
 }
 
+impl TokenType {
+    /// Whether the token is `=` or one of the compound assignments.
+    pub fn is_assignment(&self) -> bool {
+        matches!(self, TokenType::Equal) || self.compound_operator().is_some()
+    }
+
+    /// The binary operator a compound assignment applies, `a op= b` is the
+    /// same as `a = a op b`.
+    pub fn compound_operator(&self) -> Option<TokenType> {
+        match self {
+            TokenType::PlusEquals => Some(TokenType::Plus),
+            TokenType::MinusEquals => Some(TokenType::Minus),
+            TokenType::StarEquals => Some(TokenType::Star),
+            TokenType::SlashEquals => Some(TokenType::Slash),
+            TokenType::IntegerSlashEquals => Some(TokenType::IntegerSlash),
+            TokenType::ModEquals => Some(TokenType::Mod),
+            TokenType::PowerEquals => Some(TokenType::Power),
+            TokenType::BitAndEquals => Some(TokenType::BitAnd),
+            TokenType::BitOrEquals => Some(TokenType::BitOr),
+            TokenType::BitXorEquals => Some(TokenType::BitXor),
+            TokenType::ShiftLeftEqual => Some(TokenType::ShiftLeft),
+            TokenType::ShiftRightEqual => Some(TokenType::ShiftRight),
+            _ => None,
+        }
+    }
+}
+
 impl Default for TokenType {
     fn default() -> Self {
-        Self::Unknown('\0')
+        Self::Error(LexError::UnexpectedCharacter { ch: '\0' })
     }
 }
 
@@ -110,8 +151,7 @@ impl Display for TokenType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TokenType::Eof => write!(f, "{}", "EOF".red()),
-            TokenType::Unknown(ch) => write!(f, "{} {ch}", "[Unknown Symbol]".red()),
-            TokenType::InvalidByteSequenceToString => write!(f, "{}", "[InvalidByteSequenceToString]".red()),
+            TokenType::Error(e) => write!(f, "{} {e}", "[Malformed Token]".red()),
             TokenType::LeftParen => write!(f, "("),
             TokenType::RightParen => write!(f, ")"),
             TokenType::LeftSquare => write!(f, "["),
@@ -128,7 +168,8 @@ impl Display for TokenType {
             TokenType::DynamicDefinition => write!(f, ":="),
             TokenType::StaticScopeGetter => write!(f, "::"),
             TokenType::Question => write!(f, "?"),
-            TokenType::Mod => write!(f, "mod"),
+            TokenType::Mod => write!(f, "%"),
+            TokenType::ModEquals => write!(f, "%="),
             TokenType::Not => write!(f, "!"),
             TokenType::NotEqual => write!(f, "!="),
             TokenType::Equal => write!(f, "="),
@@ -143,8 +184,13 @@ impl Display for TokenType {
             TokenType::ShiftLeftEqual => write!(f, "<<="),
             TokenType::And => write!(f, "&&"),
             TokenType::BitAnd => write!(f, "&"),
+            TokenType::BitAndEquals => write!(f, "&="),
             TokenType::Or => write!(f, "||"),
             TokenType::BitOr => write!(f, "|"),
+            TokenType::BitOrEquals => write!(f, "|="),
+            TokenType::BitXor => write!(f, "^"),
+            TokenType::BitXorEquals => write!(f, "^="),
+            TokenType::BitNot => write!(f, "~"),
             TokenType::Match => write!(f, "match"),
             TokenType::Minus => write!(f, "-"),
             TokenType::MinusEquals => write!(f, "-="),
@@ -161,9 +207,14 @@ impl Display for TokenType {
             TokenType::Double => write!(f, "double precision number"),
             TokenType::Integer => write!(f, "integer number"),
             TokenType::String => write!(f, "string"),
+            TokenType::StringHead => write!(f, "start of an interpolated string"),
+            TokenType::StringMiddle => write!(f, "middle of an interpolated string"),
+            TokenType::StringTail => write!(f, "end of an interpolated string"),
+            TokenType::Char => write!(f, "character"),
             TokenType::True => write!(f, "boolean true"),
             TokenType::False => write!(f, "boolean false"),
             TokenType::Identifier => write!(f, "identifier"),
+            TokenType::DocComment => write!(f, "doc comment"),
             TokenType::Nil => write!(f, "nil"),
             TokenType::U8 => write!(f, "u8"),
             TokenType::U16 => write!(f, "u16"),
@@ -178,11 +229,13 @@ impl Display for TokenType {
             TokenType::F32 => write!(f, "f32"),
             TokenType::F64 => write!(f, "f64"),
             TokenType::StringType => write!(f, "string type"),
+            TokenType::CharType => write!(f, "char type"),
             TokenType::Void => write!(f, "void type"),
             TokenType::DontCare => write!(f, "_"),
             TokenType::Break => write!(f, "break"),
             TokenType::Continue => write!(f, "continue"),
             TokenType::Else => write!(f, "else"),
+            TokenType::As => write!(f, "as"),
             TokenType::Enum => write!(f, "enum"),
             TokenType::Fn => write!(f, "fn"),
             TokenType::For => write!(f, "for"),
@@ -1,69 +1,110 @@
-use super::{source::SourceFile, token::Token, token_type::TokenType};
+use super::{
+    error::LexError,
+    interner::BuildLexemeHasher,
+    source::SourceFile,
+    source_map::Span,
+    token::Token,
+    token_type::TokenType,
+    trivia::{LosslessToken, Trivia, TriviaKind},
+};
 use lazy_static::lazy_static;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 lazy_static! {
-    static ref KEYWORD: HashMap<String, TokenType> = {
-        HashMap::from([
-            ("_".to_owned(), TokenType::DontCare),
-            ("break".to_owned(), TokenType::Break),
-            ("bool".to_owned(), TokenType::Bool),
-            ("continue".to_owned(), TokenType::Continue),
-            ("defer".to_owned(), TokenType::Defer),
-            ("else".to_owned(), TokenType::Else),
-            ("enum".to_owned(), TokenType::Enum),
-            ("false".to_owned(), TokenType::False),
-            ("fn".to_owned(), TokenType::Fn),
-            ("for".to_owned(), TokenType::For),
-            ("if".to_owned(), TokenType::If),
-            ("import".to_owned(), TokenType::Import),
-            ("let".to_owned(), TokenType::Let),
-            ("loop".to_owned(), TokenType::Loop),
-            ("main".to_owned(), TokenType::Main),
-            ("match".to_owned(), TokenType::Match),
-            ("nil".to_owned(), TokenType::Nil),
-            ("return".to_owned(), TokenType::Return),
-            ("struct".to_owned(), TokenType::Struct),
-            ("true".to_owned(), TokenType::True),
-            ("void".to_owned(), TokenType::Void),
-            ("while".to_owned(), TokenType::While),
-            ("u8".to_owned(), TokenType::U8),
-            ("u16".to_owned(), TokenType::U16),
-            ("u32".to_owned(), TokenType::U32),
-            ("u64".to_owned(), TokenType::U64),
-            ("usize".to_owned(), TokenType::Usize),
-            ("i8".to_owned(), TokenType::I8),
-            ("i16".to_owned(), TokenType::I16),
-            ("i32".to_owned(), TokenType::I32),
-            ("i64".to_owned(), TokenType::I64),
-            ("isize".to_owned(), TokenType::Isize),
-            ("f32".to_owned(), TokenType::F32),
-            ("f64".to_owned(), TokenType::F64),
-            ("str".to_owned(), TokenType::StringType),
+    static ref KEYWORD: HashMap<&'static str, TokenType, BuildLexemeHasher> = {
+        HashMap::from_iter([
+            ("_", TokenType::DontCare),
+            ("as", TokenType::As),
+            ("break", TokenType::Break),
+            ("bool", TokenType::Bool),
+            ("char", TokenType::CharType),
+            ("continue", TokenType::Continue),
+            ("defer", TokenType::Defer),
+            ("else", TokenType::Else),
+            ("enum", TokenType::Enum),
+            ("false", TokenType::False),
+            ("fn", TokenType::Fn),
+            ("for", TokenType::For),
+            ("if", TokenType::If),
+            ("import", TokenType::Import),
+            ("let", TokenType::Let),
+            ("loop", TokenType::Loop),
+            ("main", TokenType::Main),
+            ("match", TokenType::Match),
+            ("nil", TokenType::Nil),
+            ("return", TokenType::Return),
+            ("struct", TokenType::Struct),
+            ("true", TokenType::True),
+            ("void", TokenType::Void),
+            ("while", TokenType::While),
+            ("u8", TokenType::U8),
+            ("u16", TokenType::U16),
+            ("u32", TokenType::U32),
+            ("u64", TokenType::U64),
+            ("usize", TokenType::Usize),
+            ("i8", TokenType::I8),
+            ("i16", TokenType::I16),
+            ("i32", TokenType::I32),
+            ("i64", TokenType::I64),
+            ("isize", TokenType::Isize),
+            ("f32", TokenType::F32),
+            ("f64", TokenType::F64),
+            ("str", TokenType::StringType),
         ])
     };
 }
 
-pub fn get_token(source: &mut SourceFile) -> Box<Token> {
-    skip_whitespace(source);
+pub fn get_token(source: &mut SourceFile) -> Token {
+    if let Some(token) = source.pending.pop_front() {
+        return token;
+    }
+
+    if let Some(error) = skip_whitespace(source) {
+        return error;
+    }
 
     source.start = source.current;
 
     match advance(source) {
-        0 => make_token(TokenType::Eof, source),
+        0 if source.is_at_end() => make_token(TokenType::Eof, source),
         b'"' => make_string_token(source),
-        b'0'..=b'9' => make_number_token(source),
+        b'\'' => make_char_token(source),
+        digit @ b'0'..=b'9' => make_number_token(digit, source),
+        b'r' if is_raw_string_start(source) => make_raw_string_token(source),
+        // `skip_whitespace` only stops on a `#` opening a doc comment
+        b'#' => make_doc_comment_token(source),
         b'a'..=b'z' | b'A'..=b'Z' | b'_' => make_identifier_token(source),
+        0x80.. => make_unicode_token(source),
         b'(' => make_token(TokenType::LeftParen, source),
         b')' => make_token(TokenType::RightParen, source),
         b'[' => make_token(TokenType::LeftSquare, source),
         b']' => make_token(TokenType::RightSquare, source),
-        b'{' => make_token(TokenType::LeftBrace, source),
-        b'}' => make_token(TokenType::RightBrace, source),
+        b'{' => {
+            if let Some(depth) = source.interpolations.last_mut() {
+                *depth += 1;
+            }
+            make_token(TokenType::LeftBrace, source)
+        }
+        b'}' => match source.interpolations.last_mut() {
+            // closes the innermost interpolation hole, back to lexing its string
+            Some(0) => make_string_part_token(source, false),
+            Some(depth) => {
+                *depth -= 1;
+                make_token(TokenType::RightBrace, source)
+            }
+            None => make_token(TokenType::RightBrace, source),
+        },
         b'?' => make_token(TokenType::Question, source),
         b',' => make_token(TokenType::Comma, source),
         b';' => make_token(TokenType::Semicolon, source),
-        b'%' => make_token(TokenType::Mod, source),
+        b'%' => match source.peek() {
+            b'=' => {
+                advance(source);
+                make_token(TokenType::ModEquals, source)
+            }
+            _ => make_token(TokenType::Mod, source),
+        },
+        b'~' => make_token(TokenType::BitNot, source),
         b'.' => match source.peek() {
             b'.' => {
                 advance(source);
@@ -141,6 +182,10 @@ pub fn get_token(source: &mut SourceFile) -> Box<Token> {
                 advance(source);
                 make_token(TokenType::And, source)
             }
+            b'=' => {
+                advance(source);
+                make_token(TokenType::BitAndEquals, source)
+            }
             _ => make_token(TokenType::BitAnd, source),
         },
         b'|' => match source.peek() {
@@ -148,6 +193,10 @@ pub fn get_token(source: &mut SourceFile) -> Box<Token> {
                 advance(source);
                 make_token(TokenType::Or, source)
             }
+            b'=' => {
+                advance(source);
+                make_token(TokenType::BitOrEquals, source)
+            }
             _ => make_token(TokenType::BitOr, source),
         },
         b'-' => match source.peek() {
@@ -190,295 +239,967 @@ pub fn get_token(source: &mut SourceFile) -> Box<Token> {
                 advance(source);
                 make_token(TokenType::StarEquals, source)
             }
+            b'*' => {
+                advance(source);
+                match source.peek() {
+                    b'=' => {
+                        advance(source);
+                        make_token(TokenType::PowerEquals, source)
+                    }
+                    _ => make_token(TokenType::Power, source),
+                }
+            }
             _ => make_token(TokenType::Star, source),
         },
         b'^' => match source.peek() {
             b'=' => {
                 advance(source);
-                make_token(TokenType::PowerEquals, source)
+                make_token(TokenType::BitXorEquals, source)
             }
-            _ => make_token(TokenType::Power, source),
+            _ => make_token(TokenType::BitXor, source),
         },
-        ch => make_token(TokenType::Unknown(ch as char), source),
+        ch => make_token(
+            TokenType::Error(LexError::UnexpectedCharacter { ch: ch as char }),
+            source,
+        ),
+    }
+}
+
+/// Same as `get_token`, but the whitespace and comments in front of the token
+/// are kept as trivia together with the exact text of the token.
+pub fn get_lossless_token(source: &mut SourceFile) -> LosslessToken {
+    if let Some(token) = source.pending.pop_front() {
+        // the text of the whole literal went with its first error
+        return LosslessToken {
+            leading_trivia: vec![],
+            text: String::new(),
+            token,
+        };
+    }
+
+    let mut leading_trivia: Vec<Trivia> = vec![];
+    loop {
+        let trivia_start: usize = source.current;
+        match skip_trivia(source) {
+            Some(Ok(kind)) => leading_trivia.push(Trivia {
+                kind,
+                text: source.lexeme_from(trivia_start).into_owned(),
+            }),
+            Some(Err(error)) => {
+                // an unterminated block comment runs up to the end of the file
+                leading_trivia.push(Trivia {
+                    kind: TriviaKind::BlockComment,
+                    text: source.lexeme_from(trivia_start).into_owned(),
+                });
+
+                return LosslessToken {
+                    leading_trivia,
+                    text: String::new(),
+                    token: error,
+                };
+            }
+            None => break,
+        }
+    }
+
+    let token_start: usize = source.current;
+    let token = get_token(source);
+
+    LosslessToken {
+        leading_trivia,
+        text: source.lexeme_from(token_start).into_owned(),
+        token,
     }
 }
 
 #[inline]
-fn make_token(ttype: TokenType, source: &mut SourceFile) -> Box<Token> {
-    Box::new(Token {
-        line: source.line,
-        column: source.column - (source.current - source.start),
+fn make_token(ttype: TokenType, source: &SourceFile) -> Token {
+    Token {
+        span: Span::new(source.id, source.start, source.current),
         ttype,
-        lexeme: source.build_lexeme(),
-        found_in: source.name.clone(),
-    })
+        lexeme: source.intern(&source.build_lexeme()),
+        suffix: None,
+    }
 }
 
-fn make_number_token(source: &mut SourceFile) -> Box<Token> {
-    while source.peek().is_ascii_digit() {
-        advance(source);
+fn make_number_token(first_digit: u8, source: &mut SourceFile) -> Token {
+    match (first_digit, source.peek()) {
+        (b'0', b'x' | b'X') => return make_radix_number_token(16, source),
+        (b'0', b'o' | b'O') => return make_radix_number_token(8, source),
+        (b'0', b'b' | b'B') => return make_radix_number_token(2, source),
+        _ => {}
     }
 
+    let mut literal = String::from(first_digit as char);
+    let mut is_double = false;
+    consume_decimal_digits(&mut literal, source);
+
     if source.peek() == b'.' && source.peek_next().is_ascii_digit() {
-        advance(source);
+        is_double = true;
+        literal.push(advance(source) as char);
+        consume_decimal_digits(&mut literal, source);
+    }
 
-        while source.peek().is_ascii_digit() {
-            advance(source);
+    if matches!(source.peek(), b'e' | b'E') {
+        is_double = true;
+        literal.push(advance(source) as char);
+
+        if matches!(source.peek(), b'+' | b'-') {
+            literal.push(advance(source) as char);
         }
 
-        make_token(TokenType::Double, source)
-    } else {
-        make_token(TokenType::Integer, source)
+        if !source.peek().is_ascii_digit() {
+            return make_token(TokenType::Error(LexError::MissingExponent), source);
+        }
+        consume_decimal_digits(&mut literal, source);
     }
-}
 
-fn make_string_token(source: &mut SourceFile) -> Box<Token> {
-    let mut lexeme: String = String::new();
-    while source.peek() != b'"' {
-        match source.peek() {
-            0 => break,
-            b'\\' => {
-                advance(source);
-                match advance(source) {
-                    b'n' => lexeme += "\n",
-                    b't' => lexeme += "\t",
-                    b'r' => lexeme += "\r",
-                    b'\'' => lexeme += "\'",
-                    b'"' => lexeme += "\"",
-                    b'\\' => lexeme += "\\",
-                    ch => {
-                        return make_token_from(
-                            TokenType::Unknown(ch as char),
-                            "Invalid escape sequence.",
-                            source,
-                        )
-                    }
-                }
-            }
-            _ => {
-                lexeme += &{
-                    match String::from_utf8(vec![advance(source)]) {
-                        Ok(t) => t,
-                        Err(_) => {
-                            return make_token_from(
-                                TokenType::InvalidByteSequenceToString,
-                                &lexeme,
-                                source,
-                            )
-                        }
-                    }
-                }
+    let suffix: Option<TokenType> = match consume_number_suffix(source) {
+        Ok(suffix) => suffix,
+        Err(e) => return make_token(TokenType::Error(e), source),
+    };
+
+    match suffix {
+        // `42f64` is a perfectly fine way to write a double
+        Some(TokenType::F32 | TokenType::F64) => {
+            make_number_literal(TokenType::Double, &literal, suffix, source)
+        }
+        Some(integer_suffix) if is_double => make_token(
+            TokenType::Error(LexError::InvalidSuffix {
+                suffix: integer_suffix.to_string(),
+            }),
+            source,
+        ),
+        _ if is_double => make_number_literal(TokenType::Double, &literal, suffix, source),
+        _ => match literal.parse::<u64>() {
+            Ok(value) => {
+                make_number_literal(TokenType::Integer, &value.to_string(), suffix, source)
             }
+            Err(_) => make_token(TokenType::Error(LexError::IntegerTooLarge), source),
+        },
+    }
+}
+
+fn make_radix_number_token(radix: u32, source: &mut SourceFile) -> Token {
+    // skips the radix prefix, `0` has already been consumed
+    advance(source);
+
+    let mut digits = String::new();
+    while (source.peek() as char).is_digit(radix) || source.peek() == b'_' {
+        match advance(source) {
+            b'_' => {}
+            digit => digits.push(digit as char),
         }
     }
 
-    match source.peek() {
-        b'"' => {
+    // `0b102` and `0o8` end up here since the digit loop stops at the first
+    // digit that doesn't belong to the radix.
+    if source.peek().is_ascii_digit() {
+        let digit = source.peek() as char;
+        while source.peek().is_ascii_alphanumeric() || source.peek() == b'_' {
             advance(source);
-            make_token_from(TokenType::String, &lexeme, source)
         }
-        // i don't think this can assume any other value than 0
-        unexpected_char => make_token_from(
-            TokenType::Unknown(unexpected_char as char),
-            "Unterminated string.",
+
+        return make_token(
+            TokenType::Error(LexError::InvalidDigit { radix, digit }),
             source,
-        ),
+        );
+    }
+
+    if digits.is_empty() {
+        consume_number_suffix(source).ok();
+        return make_token(TokenType::Error(LexError::MissingDigits { radix }), source);
+    }
+
+    let suffix: Option<TokenType> = match consume_number_suffix(source) {
+        Ok(Some(float_suffix @ (TokenType::F32 | TokenType::F64))) => {
+            return make_token(
+                TokenType::Error(LexError::InvalidSuffix {
+                    suffix: float_suffix.to_string(),
+                }),
+                source,
+            )
+        }
+        Ok(suffix) => suffix,
+        Err(e) => return make_token(TokenType::Error(e), source),
+    };
+
+    match u64::from_str_radix(&digits, radix) {
+        Ok(value) => make_number_literal(TokenType::Integer, &value.to_string(), suffix, source),
+        Err(_) => make_token(TokenType::Error(LexError::IntegerTooLarge), source),
     }
 }
 
-fn make_identifier_token(source: &mut SourceFile) -> Box<Token> {
+fn consume_number_suffix(source: &mut SourceFile) -> Result<Option<TokenType>, LexError> {
+    let suffix_start: usize = source.current;
     while source.peek().is_ascii_alphanumeric() || source.peek() == b'_' {
         advance(source);
     }
 
-    match KEYWORD.get(&source.build_lexeme()) {
-        Some(token_type) => make_token(token_type.clone(), source),
-        None => make_token(TokenType::Identifier, source),
+    if source.current == suffix_start {
+        return Ok(None);
+    }
+
+    let suffix = source.lexeme_from(suffix_start);
+    match KEYWORD.get(suffix.as_ref()) {
+        Some(
+            ttype @ (TokenType::U8
+            | TokenType::U16
+            | TokenType::U32
+            | TokenType::U64
+            | TokenType::Usize
+            | TokenType::I8
+            | TokenType::I16
+            | TokenType::I32
+            | TokenType::I64
+            | TokenType::Isize
+            | TokenType::F32
+            | TokenType::F64),
+        ) => Ok(Some(ttype.clone())),
+        _ => Err(LexError::InvalidSuffix {
+            suffix: suffix.into_owned(),
+        }),
+    }
+}
+
+#[inline]
+fn make_number_literal(
+    ttype: TokenType,
+    value: &str,
+    suffix: Option<TokenType>,
+    source: &mut SourceFile,
+) -> Token {
+    let mut token = make_token_from(ttype, value, source);
+    token.suffix = suffix;
+    token
+}
+
+#[inline]
+fn consume_decimal_digits(literal: &mut String, source: &mut SourceFile) {
+    while source.peek().is_ascii_digit() || source.peek() == b'_' {
+        match advance(source) {
+            b'_' => {}
+            digit => literal.push(digit as char),
+        }
+    }
+}
+
+fn make_string_token(source: &mut SourceFile) -> Token {
+    if source.peek() == b'"' && source.peek_next() == b'"' {
+        advance(source);
+        advance(source);
+        return make_multiline_string_token(source);
     }
+
+    make_string_part_token(source, true)
 }
 
-fn make_token_from(ttype: TokenType, lexeme: &str, source: &mut SourceFile) -> Box<Token> {
-    Box::new(Token {
-        line: source.line,
-        column: {
-            match ttype {
-                TokenType::Unknown(_) => source.column - 1,
-                _ => source.column - lexeme.len(),
+/// Lexes a string up to its closing `"` or up to the `{` opening an
+/// interpolation hole. `is_head` tells whether the part starts at the opening
+/// `"` or at the `}` closing the previous hole.
+fn make_string_part_token(source: &mut SourceFile, is_head: bool) -> Token {
+    let opening_end: usize = source.current;
+    let mut lexeme: String = String::new();
+    let mut errors: VecDeque<Token> = VecDeque::new();
+
+    while !matches!(source.peek(), b'"' | b'{') {
+        match source.peek() {
+            0 if source.is_at_end() => break,
+            b'\\' => consume_string_escape(source, &mut lexeme, &mut errors),
+            _ => consume_string_char(source, &mut lexeme, &mut errors),
+        }
+    }
+
+    let token = match source.peek() {
+        b'"' => {
+            advance(source);
+
+            if is_head {
+                make_token_from(TokenType::String, &lexeme, source)
+            } else {
+                source.interpolations.pop();
+                make_token_from(TokenType::StringTail, &lexeme, source)
             }
-        },
-        ttype,
-        lexeme: lexeme.to_string(),
-        found_in: source.name.clone(),
-    })
+        }
+        b'{' => {
+            advance(source);
+
+            if is_head {
+                source.interpolations.push(0);
+                make_token_from(TokenType::StringHead, &lexeme, source)
+            } else {
+                make_token_from(TokenType::StringMiddle, &lexeme, source)
+            }
+        }
+        _ => make_token_from(
+            TokenType::Error(LexError::UnterminatedString),
+            &source.lexeme_between(source.start, opening_end),
+            source,
+        ),
+    };
+
+    finish_string_token(token, errors, source)
 }
 
-fn skip_whitespace(source: &mut SourceFile) {
+/// Lexes a `"""` string, the opening `"""` has already been consumed.
+///
+/// A blank line right after the opening delimiter and the blank line holding
+/// the closing one are dropped, then the indentation shared by every
+/// remaining non blank line is stripped.
+fn make_multiline_string_token(source: &mut SourceFile) -> Token {
+    let opening_end: usize = source.current;
+    let mut errors: VecDeque<Token> = VecDeque::new();
+
+    // (indentation, content) of every line between the delimiters
+    let mut lines: Vec<(String, String)> = vec![(String::new(), String::new())];
+    let mut in_indentation = false;
+
     loop {
+        let (indentation, content) = lines.last_mut().unwrap();
+
         match source.peek() {
-            b'\n' | b'\r' => {
+            0 if source.is_at_end() => {
+                let token = make_token_from(
+                    TokenType::Error(LexError::UnterminatedString),
+                    &source.lexeme_between(source.start, opening_end),
+                    source,
+                );
+                return finish_string_token(token, errors, source);
+            }
+            b'"' if source.peek_next() == b'"' && source.peek_at(2) == b'"' => {
+                advance_by(source, 3);
+                break;
+            }
+            b'\r' if source.peek_next() == b'\n' => {
                 advance(source);
-
-                source.line += 1;
-                source.column = 0;
             }
-            b' ' | b'\t' => {
+            b'\n' => {
                 advance(source);
+
+                lines.push((String::new(), String::new()));
+                in_indentation = true;
             }
-            b'#' => {
-                match source.peek_next() {
-                    // Multi-line comment
-                    b'#' => {
-                        advance(source);
+            b' ' | b'\t' if in_indentation => indentation.push(advance(source) as char),
+            b'\\' => {
+                in_indentation = false;
+                consume_string_escape(source, content, &mut errors);
+            }
+            _ => {
+                in_indentation = false;
+                consume_string_char(source, content, &mut errors);
+            }
+        }
+    }
 
-                        while !(source.peek() == b'#' && source.peek_next() == b'#') {
-                            if matches!(advance(source), b'\n' | b'\r') {
-                                source.line += 1;
-                                source.column = 0;
-                            }
-                        }
+    let is_blank = |(_, content): &(String, String)| content.trim().is_empty();
 
-                        advance(source);
-                        advance(source);
-                    }
-                    // Single line comment
-                    _ => {
-                        while source.peek() != b'\n' {
-                            advance(source);
-                        }
-                    }
-                }
-            }
-            _ => return,
-        };
+    let opening_line: Option<(String, String)> = match lines.remove(0) {
+        line if is_blank(&line) => None,
+        line => Some(line),
+    };
+
+    if lines.last().is_some_and(is_blank) {
+        lines.pop();
     }
+
+    let common_indentation: String = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|(indentation, _)| indentation.as_str())
+        .reduce(|common, indentation| {
+            let shared = common
+                .bytes()
+                .zip(indentation.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            &common[..shared]
+        })
+        .unwrap_or_default()
+        .to_owned();
+
+    let lexeme: String = opening_line
+        .map(|(indentation, content)| indentation + &content)
+        .into_iter()
+        .chain(lines.iter().map(|line| match line {
+            line if is_blank(line) => String::new(),
+            (indentation, content) => indentation[common_indentation.len()..].to_owned() + content,
+        }))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let token = make_token_from(TokenType::String, &lexeme, source);
+    finish_string_token(token, errors, source)
 }
 
-#[inline]
-fn advance(source: &mut SourceFile) -> u8 {
-    advance_by(source, 1)
+/// Lexes `r"..."` and `r#"..."#`, the `r` has already been consumed.
+fn make_raw_string_token(source: &mut SourceFile) -> Token {
+    let mut hashes: usize = 0;
+    while source.peek() == b'#' {
+        advance(source);
+        hashes += 1;
+    }
+
+    // opening `"`
+    advance(source);
+
+    let opening_end: usize = source.current;
+    let mut lexeme = String::new();
+    let mut errors: VecDeque<Token> = VecDeque::new();
+    let token = loop {
+        match source.peek() {
+            0 if source.is_at_end() => {
+                break make_token_from(
+                    TokenType::Error(LexError::UnterminatedString),
+                    &source.lexeme_between(source.start, opening_end),
+                    source,
+                )
+            }
+            b'"' if (1..=hashes).all(|offset| source.peek_at(offset) == b'#') => {
+                advance_by(source, hashes + 1);
+                break make_token_from(TokenType::String, &lexeme, source);
+            }
+            _ => consume_string_char(source, &mut lexeme, &mut errors),
+        }
+    };
+
+    finish_string_token(token, errors, source)
 }
 
 #[inline]
-fn advance_by(source: &mut SourceFile, increment: usize) -> u8 {
-    match source.peek() {
-        0 => 0,
-        value => {
-            source.current += increment;
-            source.column += increment;
-            value
-        }
+fn is_raw_string_start(source: &SourceFile) -> bool {
+    let mut offset: usize = 0;
+    while source.peek_at(offset) == b'#' {
+        offset += 1;
     }
+
+    source.peek_at(offset) == b'"'
 }
 
-#[allow(unused_imports)]
-#[allow(dead_code)]
-mod tests {
-    use super::*;
-    use crate::test_util::{create_test_file, delete_test_file};
-    use std::{fs::File, io::Write};
+/// Appends the character under the cursor to `lexeme`, invalid UTF-8 is
+/// recorded in `errors`.
+fn consume_string_char(source: &mut SourceFile, lexeme: &mut String, errors: &mut VecDeque<Token>) {
+    let char_start: usize = source.current;
 
-    fn scan_file(path: &str) -> Vec<Box<Token>> {
-        let mut scanned: Vec<Box<Token>> = vec![];
-        let mut source = SourceFile::new(path).unwrap();
+    match consume_utf8_char(source) {
+        Some(ch) => lexeme.push(ch),
+        None => errors.push_back(make_error_at(LexError::InvalidUtf8, char_start, source)),
+    }
+}
 
-        let mut eof_found = false;
-        while !eof_found {
-            let tk = get_token(&mut source);
-            if tk.ttype == TokenType::Eof {
-                eof_found = true;
-            }
+/// Appends the escape sequence under the cursor to `lexeme`, an invalid one
+/// is recorded in `errors` as an error token at its exact position.
+fn consume_string_escape(
+    source: &mut SourceFile,
+    lexeme: &mut String,
+    errors: &mut VecDeque<Token>,
+) {
+    let escape_start: usize = source.current;
+    advance(source);
 
-            scanned.push(tk);
-        }
+    if matches!(source.peek(), b'\n' | b'\r') {
+        skip_line_continuation(source);
+        return;
+    }
 
-        return scanned;
+    match consume_escape(source) {
+        Ok(ch) => lexeme.push(ch),
+        Err(e) => errors.push_back(make_error_at(e, escape_start, source)),
     }
+}
 
-    fn test_tokentype_equality(expected: Vec<TokenType>, found: Vec<Box<Token>>) {
-        assert_eq!(
-            expected,
-            found
-                .iter()
-                .map(|tk| tk.ttype.clone())
-                .collect::<Vec<TokenType>>()
-        );
+/// Builds an error token spanning from `start` up to the cursor.
+fn make_error_at(error: LexError, start: usize, source: &SourceFile) -> Token {
+    Token {
+        span: Span::new(source.id, start, source.current),
+        ttype: TokenType::Error(error),
+        lexeme: source.intern(&source.lexeme_from(start)),
+        suffix: None,
     }
+}
 
-    #[test]
-    fn single_file_tokenization() {
-        create_test_file("single.file", "a b c d e");
+/// A literal with broken escapes or bytes is replaced by one error per
+/// problem, followed by its own error if it was never terminated.
+fn finish_string_token(
+    token: Token,
+    mut errors: VecDeque<Token>,
+    source: &mut SourceFile,
+) -> Token {
+    match errors.pop_front() {
+        Some(first_error) => {
+            source.pending.extend(errors);
+            if let TokenType::Error(_) = token.ttype {
+                source.pending.push_back(token);
+            }
+            first_error
+        }
+        None => token,
+    }
+}
 
-        let scanned: Vec<Box<Token>> = scan_file("single.file");
-        let expected = vec![
-            Box::new(Token {
-                line: 1,
-                column: 0,
-                ttype: TokenType::Identifier,
-                lexeme: String::from("a"),
-                found_in: "single.file".to_string(),
-            }),
-            Box::new(Token {
-                line: 1,
-                column: 2,
-                ttype: TokenType::Identifier,
-                lexeme: String::from("b"),
-                found_in: "single.file".to_string(),
-            }),
-            Box::new(Token {
-                line: 1,
-                column: 4,
-                ttype: TokenType::Identifier,
-                lexeme: String::from("c"),
-                found_in: "single.file".to_string(),
-            }),
-            Box::new(Token {
-                line: 1,
-                column: 6,
-                ttype: TokenType::Identifier,
-                lexeme: String::from("d"),
-                found_in: "single.file".to_string(),
-            }),
-            Box::new(Token {
-                line: 1,
-                column: 8,
-                ttype: TokenType::Identifier,
-                lexeme: String::from("e"),
-                found_in: "single.file".to_string(),
-            }),
-            Box::new(Token {
-                line: 1,
-                column: 9,
-                ttype: TokenType::Eof,
-                lexeme: String::new(),
-                found_in: "single.file".to_string(),
-            }),
-        ];
+/// Skips a `\\` at the end of a line together with the indentation of the
+/// following one.
+fn skip_line_continuation(source: &mut SourceFile) {
+    while matches!(source.peek(), b'\n' | b'\r' | b' ' | b'\t') {
+        advance(source);
+    }
+}
 
-        assert_eq!(expected.len(), scanned.len());
-        for x in 0..scanned.len() {
-            assert_eq!(expected[x], scanned[x]);
+fn make_char_token(source: &mut SourceFile) -> Token {
+    let value: char = match source.peek() {
+        b'\'' => {
+            advance(source);
+            return make_token(TokenType::Error(LexError::EmptyCharLiteral), source);
         }
+        0 | b'\n' | b'\r' => {
+            return make_token(TokenType::Error(LexError::UnterminatedCharLiteral), source)
+        }
+        b'\\' => {
+            advance(source);
+            match consume_escape(source) {
+                Ok(value) => value,
+                Err(e) => {
+                    skip_char_literal(source);
+                    return make_token(TokenType::Error(e), source);
+                }
+            }
+        }
+        _ => match consume_utf8_char(source) {
+            Some(value) => value,
+            None => {
+                skip_char_literal(source);
+                return make_token(TokenType::Error(LexError::InvalidUtf8), source);
+            }
+        },
+    };
 
-        delete_test_file("single.file");
+    match source.peek() {
+        b'\'' => {
+            advance(source);
+            make_token_from(TokenType::Char, &value.to_string(), source)
+        }
+        _ => {
+            if skip_char_literal(source) {
+                make_token(TokenType::Error(LexError::OverlongCharLiteral), source)
+            } else {
+                make_token(TokenType::Error(LexError::UnterminatedCharLiteral), source)
+            }
+        }
     }
+}
 
-    #[test]
-    fn keyword_tokenization_test() {
-        create_test_file("keyword.test", "_ break continue else enum false fn for if import let loop main match nil return struct true void while u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 str");
+/// Moves past the closing `'` of a broken character literal, returns `false`
+/// if the line ends before one is found.
+fn skip_char_literal(source: &mut SourceFile) -> bool {
+    loop {
+        match source.peek() {
+            0 | b'\n' | b'\r' => return false,
+            b'\'' => {
+                advance(source);
+                return true;
+            }
+            _ => {
+                advance(source);
+            }
+        }
+    }
+}
 
-        let scanned: Vec<Box<Token>> = scan_file("keyword.test");
-        let expected_types = vec![
-            TokenType::DontCare,
-            TokenType::Break,
-            TokenType::Continue,
-            TokenType::Else,
-            TokenType::Enum,
-            TokenType::False,
-            TokenType::Fn,
-            TokenType::For,
-            TokenType::If,
-            TokenType::Import,
-            TokenType::Let,
+/// Decodes the escape sequence following a `\\`.
+fn consume_escape(source: &mut SourceFile) -> Result<char, LexError> {
+    match advance(source) {
+        b'n' => Ok('\n'),
+        b't' => Ok('\t'),
+        b'r' => Ok('\r'),
+        b'\'' => Ok('\''),
+        b'"' => Ok('"'),
+        b'\\' => Ok('\\'),
+        b'0' => Ok('\0'),
+        b'{' => Ok('{'),
+        b'}' => Ok('}'),
+        b'x' => {
+            let mut digits = String::new();
+            while digits.len() < 2 && source.peek().is_ascii_hexdigit() {
+                digits.push(advance(source) as char);
+            }
+
+            match u8::from_str_radix(&digits, 16) {
+                Ok(value) if digits.len() == 2 && value.is_ascii() => Ok(value as char),
+                _ => Err(LexError::InvalidHexEscape),
+            }
+        }
+        b'u' => {
+            if source.peek() != b'{' {
+                return Err(LexError::InvalidUnicodeEscape);
+            }
+            advance(source);
+
+            let mut digits = String::new();
+            while source.peek().is_ascii_hexdigit() {
+                digits.push(advance(source) as char);
+            }
+
+            if source.peek() != b'}' || digits.is_empty() || digits.len() > 6 {
+                return Err(LexError::InvalidUnicodeEscape);
+            }
+            advance(source);
+
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(LexError::InvalidUnicodeEscape)
+        }
+        escape => Err(LexError::InvalidEscape {
+            escape: escape as char,
+        }),
+    }
+}
+
+/// Consumes a whole UTF-8 encoded character, `None` if the bytes under the
+/// cursor aren't valid UTF-8.
+fn consume_utf8_char(source: &mut SourceFile) -> Option<char> {
+    match source.peek_char() {
+        Some(ch) => {
+            advance_by(source, ch.len_utf8());
+            Some(ch)
+        }
+        None => {
+            advance(source);
+            None
+        }
+    }
+}
+
+fn make_unicode_token(source: &mut SourceFile) -> Token {
+    // `get_token` already consumed the first byte
+    source.current = source.start;
+
+    match consume_utf8_char(source) {
+        Some(ch) if is_identifier_start(ch) => make_identifier_token(source),
+        Some(ch) => make_token(
+            TokenType::Error(LexError::UnexpectedCharacter { ch }),
+            source,
+        ),
+        None => make_token(TokenType::Error(LexError::InvalidUtf8), source),
+    }
+}
+
+fn make_identifier_token(source: &mut SourceFile) -> Token {
+    loop {
+        match source.peek() {
+            ch if ch.is_ascii_alphanumeric() || ch == b'_' => {
+                advance(source);
+            }
+            0x80.. => match source.peek_char() {
+                Some(ch) if is_identifier_continue(ch) => {
+                    advance_by(source, ch.len_utf8());
+                }
+                _ => break,
+            },
+            _ => break,
+        }
+    }
+
+    match KEYWORD.get_key_value(source.build_lexeme().as_ref()) {
+        Some((keyword, token_type)) => Token {
+            span: Span::new(source.id, source.start, source.current),
+            ttype: token_type.clone(),
+            lexeme: source.intern(keyword),
+            suffix: None,
+        },
+        None => make_token(TokenType::Identifier, source),
+    }
+}
+
+fn make_token_from(ttype: TokenType, lexeme: &str, source: &SourceFile) -> Token {
+    Token {
+        span: Span::new(source.id, source.start, source.current),
+        ttype,
+        lexeme: source.intern(lexeme),
+        suffix: None,
+    }
+}
+
+/// Skips whitespace and comments, a block comment still open at the end of
+/// the file is returned as an error token spanning its opening `##`.
+fn skip_whitespace(source: &mut SourceFile) -> Option<Token> {
+    loop {
+        if let Err(error) = skip_trivia(source)? {
+            return Some(error);
+        }
+    }
+}
+
+/// Skips a single run of whitespace, a newline or a comment, `None` if the
+/// cursor is already on a token.
+fn skip_trivia(source: &mut SourceFile) -> Option<Result<TriviaKind, Token>> {
+    let kind = match source.peek() {
+        b'\n' => {
+            advance(source);
+            TriviaKind::Newline
+        }
+        b'\r' if source.peek_next() == b'\n' => {
+            advance_by(source, 2);
+            TriviaKind::Newline
+        }
+        b' ' | b'\t' | b'\r' => {
+            while matches!(source.peek(), b' ' | b'\t')
+                || (source.peek() == b'\r' && source.peek_next() != b'\n')
+            {
+                advance(source);
+            }
+            TriviaKind::Whitespace
+        }
+        b'#' => match source.peek_next() {
+            // Doc comment, handed to the parser as a token
+            b':' => return None,
+            // Multi-line comment
+            b'#' => match skip_block_comment(source) {
+                Some(error) => return Some(Err(error)),
+                None => TriviaKind::BlockComment,
+            },
+            // Nestable multi-line comment
+            b'[' => match skip_nested_block_comment(source) {
+                Some(error) => return Some(Err(error)),
+                None => TriviaKind::BlockComment,
+            },
+            // Single line comment
+            _ => {
+                while source.peek() != b'\n' && !source.is_at_end() {
+                    advance(source);
+                }
+                TriviaKind::LineComment
+            }
+        },
+        _ => return None,
+    };
+
+    Some(Ok(kind))
+}
+
+/// Lexes a `#:` doc comment up to the end of its line, the `#` has already
+/// been consumed.
+fn make_doc_comment_token(source: &mut SourceFile) -> Token {
+    // `:` and the space separating it from the text, if any
+    advance(source);
+    if source.peek() == b' ' {
+        advance(source);
+    }
+
+    let text_start: usize = source.current;
+    while source.peek() != b'\n' && !source.is_at_end() {
+        advance(source);
+    }
+
+    let text = source.lexeme_from(text_start);
+    make_token_from(TokenType::DocComment, text.trim_end_matches('\r'), source)
+}
+
+/// Skips a `## ... ##` comment.
+fn skip_block_comment(source: &mut SourceFile) -> Option<Token> {
+    source.start = source.current;
+    advance_by(source, 2);
+
+    while !(source.peek() == b'#' && source.peek_next() == b'#') {
+        if source.is_at_end() {
+            return Some(make_token_from(
+                TokenType::Error(LexError::UnterminatedBlockComment { closing: "##" }),
+                "##",
+                source,
+            ));
+        }
+
+        advance(source);
+    }
+
+    advance_by(source, 2);
+    None
+}
+
+/// Skips a `#[ ... ]#` comment, every `#[` nested inside it needs its own
+/// `]#` before the outer comment is closed.
+fn skip_nested_block_comment(source: &mut SourceFile) -> Option<Token> {
+    source.start = source.current;
+    advance_by(source, 2);
+
+    let mut depth: usize = 1;
+    while depth > 0 {
+        match (source.peek(), source.peek_next()) {
+            (b'#', b'[') => {
+                advance_by(source, 2);
+                depth += 1;
+            }
+            (b']', b'#') => {
+                advance_by(source, 2);
+                depth -= 1;
+            }
+            _ if source.is_at_end() => {
+                return Some(make_token_from(
+                    TokenType::Error(LexError::UnterminatedBlockComment { closing: "]#" }),
+                    "#[",
+                    source,
+                ));
+            }
+            _ => {
+                advance(source);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "unicode-identifiers")]
+#[inline]
+fn is_identifier_start(ch: char) -> bool {
+    ch == '_' || unicode_xid::UnicodeXID::is_xid_start(ch)
+}
+
+#[cfg(not(feature = "unicode-identifiers"))]
+#[inline]
+fn is_identifier_start(ch: char) -> bool {
+    ch == '_' || ch.is_ascii_alphabetic()
+}
+
+#[cfg(feature = "unicode-identifiers")]
+#[inline]
+fn is_identifier_continue(ch: char) -> bool {
+    unicode_xid::UnicodeXID::is_xid_continue(ch)
+}
+
+#[cfg(not(feature = "unicode-identifiers"))]
+#[inline]
+fn is_identifier_continue(ch: char) -> bool {
+    ch == '_' || ch.is_ascii_alphanumeric()
+}
+
+#[inline]
+fn advance(source: &mut SourceFile) -> u8 {
+    advance_by(source, 1)
+}
+
+/// Moves the cursor `increment` bytes forward without going past the end of
+/// the file.
+#[inline]
+fn advance_by(source: &mut SourceFile, increment: usize) -> u8 {
+    let value = source.peek();
+    source.current = (source.current + increment).min(source.size());
+
+    value
+}
+
+#[cfg(test)]
+#[allow(unused_imports)]
+#[allow(dead_code)]
+mod tests {
+    use super::*;
+    use crate::test_util::{create_test_file, delete_test_file};
+    use crate::tokens::source_map::{FileId, SourceMap};
+    use std::{fs::File, io::Write};
+
+    fn scan_file(path: &str) -> Vec<Token> {
+        scan_source(SourceFile::new(path).unwrap())
+    }
+
+    fn scan_source(mut source: SourceFile) -> Vec<Token> {
+        let mut scanned: Vec<Token> = vec![];
+
+        let mut eof_found = false;
+        while !eof_found {
+            let tk = get_token(&mut source);
+            if tk.ttype == TokenType::Eof {
+                eof_found = true;
+            }
+
+            scanned.push(tk);
+        }
+
+        scanned
+    }
+
+    /// 1-based line and 0-based column where `tk` starts in the file at `path`.
+    fn position(path: &str, tk: &Token) -> (usize, usize) {
+        SourceFile::new(path).unwrap().line_column(tk.span.start)
+    }
+
+    fn test_tokentype_equality(expected: Vec<TokenType>, found: Vec<Token>) {
+        assert_eq!(
+            expected,
+            found
+                .iter()
+                .map(|tk| tk.ttype.clone())
+                .collect::<Vec<TokenType>>()
+        );
+    }
+
+    #[test]
+    fn single_file_tokenization() {
+        create_test_file("single.file", "a b c d e");
+
+        let scanned: Vec<Token> = scan_file("single.file");
+        let expected = [
+            Token {
+                span: Span::new(FileId::default(), 0, 1),
+                ttype: TokenType::Identifier,
+                lexeme: "a".into(),
+                suffix: None,
+            },
+            Token {
+                span: Span::new(FileId::default(), 2, 3),
+                ttype: TokenType::Identifier,
+                lexeme: "b".into(),
+                suffix: None,
+            },
+            Token {
+                span: Span::new(FileId::default(), 4, 5),
+                ttype: TokenType::Identifier,
+                lexeme: "c".into(),
+                suffix: None,
+            },
+            Token {
+                span: Span::new(FileId::default(), 6, 7),
+                ttype: TokenType::Identifier,
+                lexeme: "d".into(),
+                suffix: None,
+            },
+            Token {
+                span: Span::new(FileId::default(), 8, 9),
+                ttype: TokenType::Identifier,
+                lexeme: "e".into(),
+                suffix: None,
+            },
+            Token {
+                span: Span::new(FileId::default(), 9, 9),
+                ttype: TokenType::Eof,
+                lexeme: "".into(),
+                suffix: None,
+            },
+        ];
+
+        assert_eq!(expected.len(), scanned.len());
+        for x in 0..scanned.len() {
+            assert_eq!(expected[x], scanned[x]);
+        }
+
+        delete_test_file("single.file");
+    }
+
+    #[test]
+    fn keyword_tokenization_test() {
+        create_test_file("keyword.test", "_ break continue else enum false fn for if import let loop main match nil return struct true void while u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 str");
+
+        let scanned: Vec<Token> = scan_file("keyword.test");
+        let expected_types = vec![
+            TokenType::DontCare,
+            TokenType::Break,
+            TokenType::Continue,
+            TokenType::Else,
+            TokenType::Enum,
+            TokenType::False,
+            TokenType::Fn,
+            TokenType::For,
+            TokenType::If,
+            TokenType::Import,
+            TokenType::Let,
             TokenType::Loop,
             TokenType::Main,
             TokenType::Match,
@@ -508,9 +1229,9 @@ mod tests {
 
     #[test]
     fn symbol_tokenization_test() {
-        create_test_file("symbol.test", "([{}]) . .. ..=, -> : := :: ?%! != = == > >= >> >>= < <= << <<= && & | || - -= + += / /=  // //= * *= ^ ^=");
+        create_test_file("symbol.test", "([{}]) . .. ..=, -> : := :: ?%! != = == > >= >> >>= < <= << <<= && & | || - -= + += / /=  // //= * *= ** **= ^ ^= %= &= |= ~");
 
-        let scanned: Vec<Box<Token>> = scan_file("symbol.test");
+        let scanned: Vec<Token> = scan_file("symbol.test");
         let expected_types = vec![
             TokenType::LeftParen,
             TokenType::LeftSquare,
@@ -556,10 +1277,688 @@ mod tests {
             TokenType::StarEquals,
             TokenType::Power,
             TokenType::PowerEquals,
+            TokenType::BitXor,
+            TokenType::BitXorEquals,
+            TokenType::ModEquals,
+            TokenType::BitAndEquals,
+            TokenType::BitOrEquals,
+            TokenType::BitNot,
             TokenType::Eof,
         ];
 
         test_tokentype_equality(expected_types, scanned);
         delete_test_file("symbol.test");
     }
+
+    #[test]
+    fn number_tokenization_test() {
+        create_test_file(
+            "number.test",
+            "42 0xFF 0Xff 0b1010 0o755 1_000_000 0x_dead_beef 3.14 1e-9 2.5E3 1e+3 007",
+        );
+
+        let scanned: Vec<Token> = scan_file("number.test");
+        let expected = vec![
+            (TokenType::Integer, "42"),
+            (TokenType::Integer, "255"),
+            (TokenType::Integer, "255"),
+            (TokenType::Integer, "10"),
+            (TokenType::Integer, "493"),
+            (TokenType::Integer, "1000000"),
+            (TokenType::Integer, "3735928559"),
+            (TokenType::Double, "3.14"),
+            (TokenType::Double, "1e-9"),
+            (TokenType::Double, "2.5E3"),
+            (TokenType::Double, "1e+3"),
+            (TokenType::Integer, "7"),
+            (TokenType::Eof, ""),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (tk.ttype.clone(), tk.lexeme.as_str()))
+                .collect::<Vec<(TokenType, &str)>>()
+        );
+        assert_eq!(
+            vec![0, 3, 8, 13, 20, 26, 36, 49, 54, 59, 65, 70, 73],
+            scanned
+                .iter()
+                .map(|tk| position("number.test", tk).1)
+                .collect::<Vec<usize>>()
+        );
+        delete_test_file("number.test");
+    }
+
+    #[test]
+    fn malformed_number_tokenization_test() {
+        create_test_file(
+            "malformed_number.test",
+            "0x 0b102 0o8 1e 2.5e+ 18446744073709551616 0x1_0000_0000_0000_0000 1..2",
+        );
+
+        let scanned: Vec<Token> = scan_file("malformed_number.test");
+        let expected_types = vec![
+            TokenType::Error(LexError::MissingDigits { radix: 16 }),
+            TokenType::Error(LexError::InvalidDigit {
+                radix: 2,
+                digit: '2',
+            }),
+            TokenType::Error(LexError::InvalidDigit {
+                radix: 8,
+                digit: '8',
+            }),
+            TokenType::Error(LexError::MissingExponent),
+            TokenType::Error(LexError::MissingExponent),
+            TokenType::Error(LexError::IntegerTooLarge),
+            TokenType::Error(LexError::IntegerTooLarge),
+            TokenType::Integer,
+            TokenType::SequenceUpTo,
+            TokenType::Integer,
+            TokenType::Eof,
+        ];
+
+        test_tokentype_equality(expected_types, scanned);
+        delete_test_file("malformed_number.test");
+    }
+
+    #[test]
+    fn number_suffix_tokenization_test() {
+        create_test_file(
+            "number_suffix.test",
+            "42u8 7i64 3.0f64 10usize 0xFFu8 0b1_i16 2f32 1e3f64 3.0u8 42abc 0x1f64",
+        );
+
+        let scanned: Vec<Token> = scan_file("number_suffix.test");
+        let expected = vec![
+            (TokenType::Integer, "42", Some(TokenType::U8)),
+            (TokenType::Integer, "7", Some(TokenType::I64)),
+            (TokenType::Double, "3.0", Some(TokenType::F64)),
+            (TokenType::Integer, "10", Some(TokenType::Usize)),
+            (TokenType::Integer, "255", Some(TokenType::U8)),
+            (TokenType::Integer, "1", Some(TokenType::I16)),
+            (TokenType::Double, "2", Some(TokenType::F32)),
+            (TokenType::Double, "1e3", Some(TokenType::F64)),
+            (
+                TokenType::Error(LexError::InvalidSuffix {
+                    suffix: "u8".to_owned(),
+                }),
+                "3.0u8",
+                None,
+            ),
+            (
+                TokenType::Error(LexError::InvalidSuffix {
+                    suffix: "abc".to_owned(),
+                }),
+                "42abc",
+                None,
+            ),
+            (TokenType::Integer, "8036", None),
+            (TokenType::Eof, "", None),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (tk.ttype.clone(), tk.lexeme.as_str(), tk.suffix.clone()))
+                .collect::<Vec<(TokenType, &str, Option<TokenType>)>>()
+        );
+        delete_test_file("number_suffix.test");
+    }
+
+    #[test]
+    fn char_tokenization_test() {
+        create_test_file(
+            "char.test",
+            "'a' '\\n' '\\'' '\\u{1F600}' 'é' char '' 'ab' '\\q' '\\u{D800}' 'a",
+        );
+
+        let scanned: Vec<Token> = scan_file("char.test");
+        let expected = vec![
+            (TokenType::Char, "a"),
+            (TokenType::Char, "\n"),
+            (TokenType::Char, "'"),
+            (TokenType::Char, "\u{1F600}"),
+            (TokenType::Char, "é"),
+            (TokenType::CharType, "char"),
+            (TokenType::Error(LexError::EmptyCharLiteral), "''"),
+            (TokenType::Error(LexError::OverlongCharLiteral), "'ab'"),
+            (
+                TokenType::Error(LexError::InvalidEscape { escape: 'q' }),
+                "'\\q'",
+            ),
+            (
+                TokenType::Error(LexError::InvalidUnicodeEscape),
+                "'\\u{D800}'",
+            ),
+            (TokenType::Error(LexError::UnterminatedCharLiteral), "'a"),
+            (TokenType::Eof, ""),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (tk.ttype.clone(), tk.lexeme.as_str()))
+                .collect::<Vec<(TokenType, &str)>>()
+        );
+        delete_test_file("char.test");
+    }
+
+    #[test]
+    fn utf8_string_tokenization_test() {
+        create_test_file("utf8_string.test", "\"é\" \"日本\" \"naïve ✓\" x");
+
+        let scanned: Vec<Token> = scan_file("utf8_string.test");
+        let expected = vec![
+            (TokenType::String, "é", 0),
+            (TokenType::String, "日本", 4),
+            (TokenType::String, "naïve ✓", 9),
+            (TokenType::Identifier, "x", 19),
+            (TokenType::Eof, "", 20),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (
+                    tk.ttype.clone(),
+                    tk.lexeme.as_str(),
+                    position("utf8_string.test", tk).1
+                ))
+                .collect::<Vec<(TokenType, &str, usize)>>()
+        );
+        delete_test_file("utf8_string.test");
+    }
+
+    #[cfg(feature = "unicode-identifiers")]
+    #[test]
+    fn unicode_identifier_tokenization_test() {
+        create_test_file("unicode_identifier.test", "café 日本語 _ñ x²y 😀 a");
+
+        let scanned: Vec<Token> = scan_file("unicode_identifier.test");
+        let expected = vec![
+            (TokenType::Identifier, "café", 0),
+            (TokenType::Identifier, "日本語", 5),
+            (TokenType::Identifier, "_ñ", 9),
+            (TokenType::Identifier, "x", 12),
+            (
+                TokenType::Error(LexError::UnexpectedCharacter { ch: '²' }),
+                "²",
+                13,
+            ),
+            (TokenType::Identifier, "y", 14),
+            (
+                TokenType::Error(LexError::UnexpectedCharacter { ch: '😀' }),
+                "😀",
+                16,
+            ),
+            (TokenType::Identifier, "a", 18),
+            (TokenType::Eof, "", 19),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (
+                    tk.ttype.clone(),
+                    tk.lexeme.as_str(),
+                    position("unicode_identifier.test", tk).1
+                ))
+                .collect::<Vec<(TokenType, &str, usize)>>()
+        );
+        delete_test_file("unicode_identifier.test");
+    }
+
+    #[test]
+    fn string_escape_tokenization_test() {
+        create_test_file(
+            "string_escape.test",
+            "\"\\u{48}\\x69\\0\\u{1F600}\" \"a\\\n     b\" x",
+        );
+
+        let scanned: Vec<Token> = scan_file("string_escape.test");
+        let expected = vec![
+            (TokenType::String, "Hi\0😀", 1, 0),
+            (TokenType::String, "ab", 1, 24),
+            (TokenType::Identifier, "x", 2, 8),
+            (TokenType::Eof, "", 2, 9),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("string_escape.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("string_escape.test");
+    }
+
+    #[test]
+    fn invalid_string_escape_tokenization_test() {
+        create_test_file(
+            "invalid_string_escape.test",
+            "\"ok \\q then \\x80 and \\u{110000}\" x",
+        );
+
+        let scanned: Vec<Token> = scan_file("invalid_string_escape.test");
+        let expected = vec![
+            (
+                TokenType::Error(LexError::InvalidEscape { escape: 'q' }),
+                "\\q",
+                4,
+            ),
+            (TokenType::Error(LexError::InvalidHexEscape), "\\x80", 12),
+            (
+                TokenType::Error(LexError::InvalidUnicodeEscape),
+                "\\u{110000}",
+                21,
+            ),
+            (TokenType::Identifier, "x", 33),
+            (TokenType::Eof, "", 34),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (
+                    tk.ttype.clone(),
+                    tk.lexeme.as_str(),
+                    position("invalid_string_escape.test", tk).1
+                ))
+                .collect::<Vec<(TokenType, &str, usize)>>()
+        );
+        delete_test_file("invalid_string_escape.test");
+    }
+
+    #[test]
+    fn raw_string_tokenization_test() {
+        create_test_file(
+            "raw_string.test",
+            "r\"C:\\path\\{x}\" r#\"say \"hi\"\"# r##\"a\"#b\"## r\"line\nbreak\" r x",
+        );
+
+        let scanned: Vec<Token> = scan_file("raw_string.test");
+        let expected = vec![
+            (TokenType::String, "C:\\path\\{x}", 1, 0),
+            (TokenType::String, "say \"hi\"", 1, 15),
+            (TokenType::String, "a\"#b", 1, 29),
+            (TokenType::String, "line\nbreak", 1, 41),
+            (TokenType::Identifier, "r", 2, 7),
+            (TokenType::Identifier, "x", 2, 9),
+            (TokenType::Eof, "", 2, 10),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("raw_string.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("raw_string.test");
+    }
+
+    #[test]
+    fn multiline_string_tokenization_test() {
+        create_test_file(
+            "multiline_string.test",
+            "\"\"\"\n    SELECT *\n      FROM t\n\n    WHERE x = \\\"1\\\"\n    \"\"\" \"\"\"inline\"\"\" \"\" x",
+        );
+
+        let scanned: Vec<Token> = scan_file("multiline_string.test");
+        let expected = vec![
+            (
+                TokenType::String,
+                "SELECT *\n  FROM t\n\nWHERE x = \"1\"",
+                1,
+                0,
+            ),
+            (TokenType::String, "inline", 6, 8),
+            (TokenType::String, "", 6, 21),
+            (TokenType::Identifier, "x", 6, 24),
+            (TokenType::Eof, "", 6, 25),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("multiline_string.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("multiline_string.test");
+    }
+
+    #[test]
+    fn interpolated_string_tokenization_test() {
+        create_test_file(
+            "interpolated_string.test",
+            "\"x = {x}, sum = {a + b}\" \"{ {1} }{\"in{y}\"}\\{z\\}\" }",
+        );
+
+        let scanned: Vec<Token> = scan_file("interpolated_string.test");
+        let expected = vec![
+            (TokenType::StringHead, "x = "),
+            (TokenType::Identifier, "x"),
+            (TokenType::StringMiddle, ", sum = "),
+            (TokenType::Identifier, "a"),
+            (TokenType::Plus, "+"),
+            (TokenType::Identifier, "b"),
+            (TokenType::StringTail, ""),
+            (TokenType::StringHead, ""),
+            (TokenType::LeftBrace, "{"),
+            (TokenType::Integer, "1"),
+            (TokenType::RightBrace, "}"),
+            (TokenType::StringMiddle, ""),
+            (TokenType::StringHead, "in"),
+            (TokenType::Identifier, "y"),
+            (TokenType::StringTail, ""),
+            (TokenType::StringTail, "{z}"),
+            (TokenType::RightBrace, "}"),
+            (TokenType::Eof, ""),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| (tk.ttype.clone(), tk.lexeme.as_str()))
+                .collect::<Vec<(TokenType, &str)>>()
+        );
+        delete_test_file("interpolated_string.test");
+    }
+
+    #[test]
+    fn unterminated_comment_tokenization_test() {
+        create_test_file("line_comment_at_eof.test", "a # no trailing newline");
+        let scanned: Vec<Token> = scan_file("line_comment_at_eof.test");
+        test_tokentype_equality(vec![TokenType::Identifier, TokenType::Eof], scanned);
+        delete_test_file("line_comment_at_eof.test");
+
+        create_test_file("block_comment_at_eof.test", "a\n  ## never\nclosed #");
+        let scanned: Vec<Token> = scan_file("block_comment_at_eof.test");
+        let expected = vec![
+            (TokenType::Identifier, "a", 1, 0),
+            (
+                TokenType::Error(LexError::UnterminatedBlockComment { closing: "##" }),
+                "##",
+                2,
+                2,
+            ),
+            (TokenType::Eof, "", 3, 8),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("block_comment_at_eof.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("block_comment_at_eof.test");
+    }
+
+    #[test]
+    fn lexical_error_tokenization_test() {
+        let mut file = File::create("lexical_error.test").unwrap();
+        file.write_all(b"a @ \0 b\r\n\"ok\xFF\" \"open").unwrap();
+
+        let scanned: Vec<Token> = scan_file("lexical_error.test");
+        let expected = vec![
+            (TokenType::Identifier, "a", 1, 0),
+            (
+                TokenType::Error(LexError::UnexpectedCharacter { ch: '@' }),
+                "@",
+                1,
+                2,
+            ),
+            (
+                TokenType::Error(LexError::UnexpectedCharacter { ch: '\0' }),
+                "\0",
+                1,
+                4,
+            ),
+            (TokenType::Identifier, "b", 1, 6),
+            (TokenType::Error(LexError::InvalidUtf8), "\u{FFFD}", 2, 3),
+            (TokenType::Error(LexError::UnterminatedString), "\"", 2, 6),
+            (TokenType::Eof, "", 2, 11),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("lexical_error.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("lexical_error.test");
+    }
+
+    #[test]
+    fn nested_block_comment_tokenization_test() {
+        create_test_file(
+            "nested_block_comment.test",
+            "a #[ outer\n  #[ inner ]# ## old ## ]# b ## still ## c\n#[ #[ ]#\n",
+        );
+
+        let scanned: Vec<Token> = scan_file("nested_block_comment.test");
+        let expected = vec![
+            (TokenType::Identifier, "a", 1, 0),
+            (TokenType::Identifier, "b", 2, 27),
+            (TokenType::Identifier, "c", 2, 41),
+            (
+                TokenType::Error(LexError::UnterminatedBlockComment { closing: "]#" }),
+                "#[",
+                3,
+                0,
+            ),
+            (TokenType::Eof, "", 4, 0),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("nested_block_comment.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("nested_block_comment.test");
+    }
+
+    #[test]
+    fn doc_comment_tokenization_test() {
+        create_test_file(
+            "doc_comment.test",
+            "#: Adds two numbers.\r\n  #:no space\n# plain\nfn #:\n",
+        );
+
+        let scanned: Vec<Token> = scan_file("doc_comment.test");
+        let expected = vec![
+            (TokenType::DocComment, "Adds two numbers.", 1, 0),
+            (TokenType::DocComment, "no space", 2, 2),
+            (TokenType::Fn, "fn", 4, 0),
+            (TokenType::DocComment, "", 4, 3),
+            (TokenType::Eof, "", 5, 0),
+        ];
+
+        assert_eq!(
+            expected,
+            scanned
+                .iter()
+                .map(|tk| {
+                    let (line, column) = position("doc_comment.test", tk);
+                    (tk.ttype.clone(), tk.lexeme.as_str(), line, column)
+                })
+                .collect::<Vec<(TokenType, &str, usize, usize)>>()
+        );
+        delete_test_file("doc_comment.test");
+    }
+
+    #[test]
+    fn lossless_tokenization_test() {
+        let content = "## header ##\r\nfn main() {\t# note\n    let x = 1_000 + 0x1F; #[ a #[ b ]# ]#\n  \"a\\tb{x}\\q\" r#\"raw\"# 'c'\n#: doc\n} ## open";
+        create_test_file("lossless.test", content);
+
+        let mut source = SourceFile::new("lossless.test").unwrap();
+        let mut scanned: Vec<LosslessToken> = vec![];
+        loop {
+            let token = get_lossless_token(&mut source);
+            let eof_found = token.token.ttype == TokenType::Eof;
+
+            scanned.push(token);
+            if eof_found {
+                break;
+            }
+        }
+
+        assert_eq!(
+            content,
+            scanned
+                .iter()
+                .map(LosslessToken::to_string)
+                .collect::<String>()
+        );
+        assert_eq!(
+            vec![
+                (TriviaKind::BlockComment, "## header ##"),
+                (TriviaKind::Newline, "\r\n"),
+            ],
+            scanned[0]
+                .leading_trivia
+                .iter()
+                .map(|trivia| (trivia.kind, trivia.text.as_str()))
+                .collect::<Vec<(TriviaKind, &str)>>()
+        );
+        assert_eq!(
+            vec![
+                (TriviaKind::Whitespace, "\t"),
+                (TriviaKind::LineComment, "# note"),
+                (TriviaKind::Newline, "\n"),
+                (TriviaKind::Whitespace, "    "),
+            ],
+            scanned[5]
+                .leading_trivia
+                .iter()
+                .map(|trivia| (trivia.kind, trivia.text.as_str()))
+                .collect::<Vec<(TriviaKind, &str)>>()
+        );
+        assert_eq!(
+            (TokenType::Let, "let"),
+            (scanned[5].token.ttype.clone(), scanned[5].text.as_str())
+        );
+        assert_eq!("1_000", scanned[8].text);
+        assert_eq!("1000", scanned[8].token.lexeme);
+        delete_test_file("lossless.test");
+    }
+
+    #[test]
+    fn in_memory_source_tokenization_test() {
+        let content =
+            "fn main() -> i32 {\r\n  ## c ## \"a{1_0u8}\\q\" '\\n' @ \"\"\"\n  x\n  \"\"\" #: d\n}";
+        create_test_file("in_memory_source.test", content);
+
+        let from_file: Vec<Token> = scan_file("in_memory_source.test");
+        assert_eq!(
+            from_file,
+            scan_source(SourceFile::from_string(
+                "in_memory_source.test",
+                content.to_owned()
+            ))
+        );
+        assert_eq!(
+            from_file,
+            scan_source(SourceFile::from_bytes(
+                "in_memory_source.test",
+                content.as_bytes()
+            ))
+        );
+        delete_test_file("in_memory_source.test");
+    }
+
+    #[test]
+    fn source_map_tokenization_test() {
+        let mut map = SourceMap::new();
+        let mut first = SourceFile::from_string("first.test", "a".to_owned());
+        let mut second = SourceFile::from_string("second.test", "x\n\"b\nc\" é".to_owned());
+        map.add(&mut first);
+        map.add(&mut second);
+
+        let a = get_token(&mut first);
+        let x = get_token(&mut second);
+        let string = get_token(&mut second);
+        let e = get_token(&mut second);
+
+        assert_eq!(Some(a.span.file), map.find("first.test"));
+        assert_eq!(Some(x.span.file), map.find("second.test"));
+        assert_ne!(a.span.file, x.span.file);
+        assert_eq!("second.test", map.name(string.span.file));
+
+        // the string spans two lines, the span covers it from quote to quote
+        assert_eq!((2, 0), map.line_column(string.span));
+        assert_eq!(
+            (3, 2),
+            map.line_column(Span {
+                start: string.span.end,
+                ..string.span
+            })
+        );
+        assert_eq!((2, 7), (string.span.start, string.span.end));
+
+        // columns are counted in characters, not bytes
+        assert_eq!((3, 3), map.line_column(e.span));
+        assert_eq!(2, e.span.end - e.span.start);
+    }
+
+    #[test]
+    fn interned_lexeme_tokenization_test() {
+        let scanned = scan_source(SourceFile::from_string(
+            "interned_lexeme.test",
+            "point point fn fn \"point\"".to_owned(),
+        ));
+
+        // equal lexemes share the same text, keywords included
+        let shared = |a: &Token, b: &Token| std::ptr::eq(a.lexeme.as_str(), b.lexeme.as_str());
+        assert!(shared(&scanned[0], &scanned[1]));
+        assert!(shared(&scanned[0], &scanned[4]));
+        assert!(shared(&scanned[2], &scanned[3]));
+        assert!(!shared(&scanned[0], &scanned[2]));
+    }
+
+    #[test]
+    fn lexemes_belong_to_their_file() {
+        let first = scan_source(SourceFile::from_string("first.test", "x".to_owned()));
+        let second = scan_source(SourceFile::from_string("second.test", "x".to_owned()));
+
+        // every file owns its lexemes, they go away with the file and its tokens
+        assert_eq!(first[0].lexeme, second[0].lexeme);
+        assert!(!std::ptr::eq(
+            first[0].lexeme.as_str(),
+            second[0].lexeme.as_str()
+        ));
+    }
 }
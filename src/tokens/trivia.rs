@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+use super::token::Token;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TriviaKind {
+    Whitespace,
+    Newline,
+    LineComment,
+    BlockComment,
+}
+
+/// Source text that doesn't make up a token, kept verbatim.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+}
+
+/// A token together with the exact text it was lexed from and the trivia
+/// preceding it. Writing out every token of a valid UTF-8 file, up to and
+/// including `Eof`, gives back the file byte for byte.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LosslessToken {
+    pub leading_trivia: Vec<Trivia>,
+    pub text: String,
+    pub token: Token,
+}
+
+impl Display for LosslessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for trivia in self.leading_trivia.iter() {
+            write!(f, "{}", trivia.text)?;
+        }
+
+        write!(f, "{}", self.text)
+    }
+}